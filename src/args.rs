@@ -0,0 +1,805 @@
+use
+{
+	crate::{entry::{Entry, Spec}, wrap::Wrap},
+
+	syn::{ext::IdentExt, parse::{Parse, ParseStream}, token, Attribute, Ident, LitInt, LitStr, Path, Token, Type, Visibility},
+};
+
+/// The names of the options which can be passed to the `#[errors]` macro alongside its entries.
+const OPTIONS: &[&str] = &["auto_disambiguate", "backend", "backtrace", "capture", "clone", "cold", "debug", "declare_only", "defer", "defmt", "derive", "diagnostic", "display_code", "display_prefix", "doc", "dyn_impls", "enum_attrs", "eq_kind", "errors_doc", "flatten", "helpers", "infer", "into", "into_io", "into_response", "keep_suffix", "kind", "location", "log", "max_size", "message", "module", "must_use", "name", "naming", "nested", "no_display", "no_std", "non_exhaustive", "option", "option_display", "order", "other", "passthrough", "report_debug", "result_alias", "send", "send_sync", "serialize", "set", "share", "skip", "strict", "sync", "tracing", "variant_prefix", "variant_suffix", "vis", "wrap"];
+
+/// The arguments passed to the `#[errors]` macro.
+#[derive(Default)]
+pub(crate) struct Args
+{
+	/// Whether to lengthen derived variant names which collide, rather than reporting an error.
+	pub(crate) auto_disambiguate: bool,
+
+	/// What implements the traits of the generated `Error`, if not this macro.
+	pub(crate) backend: Option<Backend>,
+
+	/// Whether each variant captures a backtrace when it is created.
+	pub(crate) backtrace: bool,
+
+	/// The arguments of the function to capture into the generated `Error`.
+	pub(crate) capture: Vec<Ident>,
+
+	/// How the generated `Error` is made `Clone`, if it is.
+	pub(crate) clone: Option<CloneBy>,
+
+	/// Whether the functions which construct the generated `Error` are marked `#[cold]` and
+	/// `#[inline(never)]`.
+	pub(crate) cold: bool,
+
+	/// Whether to print the expansion of the macro while compiling.
+	pub(crate) debug: bool,
+
+	/// Whether the function is already written to return the generated `Error`, in which case its
+	/// signature is left as it is.
+	pub(crate) declare_only: bool,
+
+	/// The `defer` option, if the generated items should be emitted by `emit_errors!` rather than
+	/// beside the function.
+	pub(crate) defer: Option<Ident>,
+
+	/// Whether to implement `defmt::Format` for the generated `Error`.
+	pub(crate) defmt: bool,
+
+	/// The traits to derive for the generated `Error`, in addition to `Debug`.
+	pub(crate) derives: Vec<Path>,
+
+	/// Whether to implement `miette::Diagnostic` for the generated `Error`.
+	pub(crate) diagnostic: bool,
+
+	/// Whether to include the code of each variant in the `Display` of the generated `Error`.
+	pub(crate) display_code: bool,
+
+	/// Whether to prefix the `Display` of each variant, and with what if not the function's name.
+	pub(crate) display_prefix: Option<Option<LitStr>>,
+
+	/// The documentation of the generated `Error`, if it should replace the default.
+	pub(crate) doc: Option<LitStr>,
+
+	/// Whether to implement `AsRef<dyn Error>` for the generated `Error`, and assert that it converts
+	/// into a `Box<dyn Error + Send + Sync>`.
+	pub(crate) dyn_impls: bool,
+
+	/// Attributes to place on the generated `Error`.
+	pub(crate) enum_attrs: Vec<Attribute>,
+
+	/// Whether to compare the generated `Error` with its kind (or another `Error`) by variant alone,
+	/// which also generates the kind.
+	pub(crate) eq_kind: bool,
+
+	/// Whether to append an "Errors" section to the documentation of the function.
+	pub(crate) errors_doc: bool,
+
+	/// The `Error`s which may be returned by the function.
+	pub(crate) entries: Vec<Entry>,
+
+	/// The existing `Error` which the function returns, given by the `use` option (or the `share`
+	/// option alone), in which case nothing is generated.
+	pub(crate) existing: Option<Type>,
+
+	/// Whether to generate helper methods for each variant of the generated `Error`.
+	pub(crate) helpers: bool,
+
+	/// Whether to add the `Error`s propagated by the `?`s in the body of the function which can be
+	/// recognized.
+	pub(crate) infer: bool,
+
+	/// The `Error`s which the generated `Error` is converted into by re-dispatching the `Error`
+	/// wrapped by each variant.
+	pub(crate) into: Vec<Type>,
+
+	/// Whether to convert the generated `Error` into a [`std::io::Error`].
+	pub(crate) into_io: bool,
+
+	/// Whether to implement `axum::response::IntoResponse` for the generated `Error`.
+	pub(crate) into_response: bool,
+
+	/// Whether to keep the trailing "Error" of each path when deriving variant names.
+	pub(crate) keep_suffix: bool,
+
+	/// Whether to generate a fieldless `enum` of the variants of the generated `Error`.
+	pub(crate) kind: bool,
+
+	/// Whether each variant captures the location where it is created.
+	pub(crate) location: bool,
+
+	/// The level at which each conversion into the generated `Error` is logged through `log`, if it
+	/// is.
+	pub(crate) log: Option<Level>,
+
+	/// The size in bytes which the generated `Error` is asserted not to exceed, if any.
+	pub(crate) max_size: Option<LitInt>,
+
+	/// Whether to declare the generated items in a module of their own (which they are re-exported
+	/// from), and what to name it if not the default.
+	pub(crate) module: Option<Option<Ident>>,
+
+	/// Whether to mark the generated `Error` as `#[must_use]`, and with what message if any.
+	pub(crate) must_use: Option<Option<LitStr>>,
+
+	/// The name of the generated `Error`, if it should not be derived from the function's.
+	pub(crate) name: Option<Ident>,
+
+	/// How variant names are derived from the paths of the `entries`.
+	pub(crate) naming: Option<Naming>,
+
+	/// Whether the functions of nested modules are rewritten too, when the macro is given to a
+	/// module.
+	pub(crate) nested: bool,
+
+	/// Whether the `Display` of the generated `Error` is left to be implemented by hand.
+	pub(crate) no_display: bool,
+
+	/// Whether the generated items refer to `core` and `alloc` rather than `std`.
+	pub(crate) no_std: bool,
+
+	/// Whether to mark the generated `Error` as `#[non_exhaustive]`.
+	pub(crate) non_exhaustive: bool,
+
+	/// The `Display` of the variant added by the `option` option, if it should not be derived from
+	/// its name.
+	pub(crate) option_display: Option<LitStr>,
+
+	/// The order of the variants of the generated `Error`.
+	pub(crate) order: Option<Order>,
+
+	/// Whether a catch-all variant for boxed `Error`s has been added to the `entries`.
+	pub(crate) other: bool,
+
+	/// Whether the `Error` of the `Result` which the function already returns is merged into the
+	/// generated `Error`, rather than wrapped by it.
+	pub(crate) passthrough: bool,
+
+	/// Whether to implement `Debug` by reporting the `Display` of the generated `Error` and each of
+	/// its sources, rather than deriving it.
+	pub(crate) report_debug: bool,
+
+	/// Whether to generate a `Result` alias, and what to name it if not the default.
+	pub(crate) result_alias: Option<Option<Ident>>,
+
+	/// Whether each `Error` is asserted to be `Send`.
+	pub(crate) send: bool,
+
+	/// Whether to implement `serde::Serialize` for the generated `Error`.
+	pub(crate) serialize: bool,
+
+	/// The set given to the `set` option which has yet to be expanded, if any.
+	pub(crate) set: Option<Path>,
+
+	/// The name of the `Error` returned by several functions, if it is shared. It is generated by
+	/// the function which lists its `Error`s, and returned by those given nothing else.
+	pub(crate) share: Option<Ident>,
+
+	/// The `skip` option, given to a method which should be left as it is by the `Error`s of its
+	/// `impl` block.
+	pub(crate) skip: Option<Ident>,
+
+	/// Whether to reject listed `Error`s which are provably never propagated by the function.
+	pub(crate) strict: bool,
+
+	/// Whether each `Error` is asserted to be `Sync`.
+	pub(crate) sync: bool,
+
+	/// The level at which each conversion into the generated `Error` is recorded as a `tracing`
+	/// event, if it is.
+	pub(crate) tracing: Option<Level>,
+
+	/// What the derived name of each variant is prefixed with, if anything.
+	pub(crate) variant_prefix: Option<LitStr>,
+
+	/// What the derived name of each variant is suffixed with, if anything.
+	pub(crate) variant_suffix: Option<LitStr>,
+
+	/// The visibility of the generated `Error`, if it should differ from the function's.
+	pub(crate) vis: Option<Visibility>,
+
+	/// What the `Error`s are returned by, if not the function itself.
+	pub(crate) wrap: Option<Wrap>,
+}
+
+impl Args
+{
+	/// Whether the next argument in the `input` is an option rather than an [`Entry`].
+	fn peek_option(input: ParseStream) -> bool
+	{
+		input.peek(Ident) && !input.peek2(Token![::]) &&
+			input.fork().parse::<Ident>().is_ok_and(|ident| OPTIONS.contains(&ident.to_string().as_str()))
+	}
+
+	/// The next argument in the `input`, if it is a lowercase identifier which is not an option
+	/// (and so is most likely a misspelled one, since `Error`s are named in `CamelCase`).
+	fn peek_misspelled(input: ParseStream) -> Option<Ident>
+	{
+		if !input.peek(Ident) || input.peek2(Token![::])
+		{
+			return None;
+		}
+
+		input.fork().parse::<Ident>().ok().filter(|ident|
+		{
+			let name = ident.to_string();
+			name.chars().all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_')
+		})
+	}
+
+	/// Parse the next option in the `input`.
+	fn parse_option(&mut self, input: ParseStream) -> syn::Result<()>
+	{
+		let name: Ident = input.parse()?;
+		match name.to_string().as_str()
+		{
+			"auto_disambiguate" => set_flag(&mut self.auto_disambiguate, &name),
+			"backend" =>
+			{
+				input.parse::<Token![=]>()?;
+				let lit: LitStr = input.parse()?;
+				let backend = match lit.value().as_str()
+				{
+					"thiserror" => Backend::Thiserror,
+					_ => return Err(syn::Error::new_spanned(lit, "unknown backend; expected `backend = \"thiserror\"`")),
+				};
+
+				set_once(&mut self.backend, backend, &name)
+			},
+			"backtrace" => set_flag(&mut self.backtrace, &name),
+			"capture" =>
+			{
+				let content;
+				syn::parenthesized!(content in input);
+				self.capture.extend(content.parse_terminated::<_, Token![,]>(Ident::parse_any)?);
+				Ok(())
+			},
+			"clone" =>
+			{
+				input.parse::<Token![=]>()?;
+				let lit: LitStr = input.parse()?;
+				let clone = match lit.value().as_str()
+				{
+					"arc" => CloneBy::Arc,
+					_ => return Err(syn::Error::new_spanned(lit, "expected `clone = \"arc\"`")),
+				};
+
+				set_once(&mut self.clone, clone, &name)
+			},
+			"cold" => set_flag(&mut self.cold, &name),
+			"debug" => set_flag(&mut self.debug, &name),
+			"declare_only" => set_flag(&mut self.declare_only, &name),
+			"defer" => set_once(&mut self.defer, name.clone(), &name),
+			"defmt" => match cfg!(feature = "defmt")
+			{
+				true => set_flag(&mut self.defmt, &name),
+				false => Err(syn::Error::new_spanned(&name, "`defmt` requires the `defmt` feature of `erro-rs`")),
+			},
+			"derive" =>
+			{
+				let content;
+				syn::parenthesized!(content in input);
+				self.derives.extend(content.parse_terminated::<_, Token![,]>(Path::parse_mod_style)?);
+				Ok(())
+			},
+			"diagnostic" => match cfg!(feature = "miette")
+			{
+				true => set_flag(&mut self.diagnostic, &name),
+				false => Err(syn::Error::new_spanned(&name, "`diagnostic` requires the `miette` feature of `erro-rs`")),
+			},
+			"display_code" => set_flag(&mut self.display_code, &name),
+			"display_prefix" =>
+			{
+				let prefix = match input.parse::<Option<Token![=]>>()?
+				{
+					Some(_) => Some(input.parse()?),
+					None => None,
+				};
+
+				set_once(&mut self.display_prefix, prefix, &name)
+			},
+			"doc" =>
+			{
+				input.parse::<Token![=]>()?;
+				let doc = input.parse()?;
+				set_once(&mut self.doc, doc, &name)
+			},
+			"dyn_impls" => set_flag(&mut self.dyn_impls, &name),
+			"enum_attrs" =>
+			{
+				let content;
+				syn::parenthesized!(content in input);
+				while !content.is_empty()
+				{
+					let attrs = content.call(Attribute::parse_outer)?;
+					if attrs.is_empty()
+					{
+						return Err(content.error("expected an attribute, e.g. `#[doc(hidden)]`"));
+					}
+
+					self.enum_attrs.extend(attrs);
+					if content.is_empty()
+					{
+						break;
+					}
+
+					content.parse::<Token![,]>()?;
+				}
+
+				Ok(())
+			},
+			"eq_kind" => set_flag(&mut self.eq_kind, &name),
+			"errors_doc" => set_flag(&mut self.errors_doc, &name),
+			"flatten" =>
+			{
+				let content;
+				syn::parenthesized!(content in input);
+				while !content.is_empty()
+				{
+					let inner = content.call(Path::parse_mod_style)?;
+					let variants;
+					syn::braced!(variants in content);
+					for (variant, ty) in variants.parse_terminated::<_, Token![,]>(|input|
+					{
+						let variant = input.call(Ident::parse_any)?;
+						input.parse::<Token![:]>()?;
+						Ok((variant, input.parse()?))
+					})?
+					{
+						self.entries.push(Entry::flattened(&inner, &variant, ty)?);
+					}
+
+					if content.is_empty()
+					{
+						break;
+					}
+
+					content.parse::<Token![,]>()?;
+				}
+
+				Ok(())
+			},
+			"helpers" => set_flag(&mut self.helpers, &name),
+			"infer" => set_flag(&mut self.infer, &name),
+			"into" =>
+			{
+				input.parse::<Token![=]>()?;
+				self.into.push(input.parse()?);
+				Ok(())
+			},
+			"into_io" => set_flag(&mut self.into_io, &name),
+			"into_response" => match cfg!(feature = "axum")
+			{
+				true => set_flag(&mut self.into_response, &name),
+				false => Err(syn::Error::new_spanned(&name, "`into_response` requires the `axum` feature of `erro-rs`")),
+			},
+			"keep_suffix" => set_flag(&mut self.keep_suffix, &name),
+			"kind" => set_flag(&mut self.kind, &name),
+			"location" => set_flag(&mut self.location, &name),
+			"log" => match cfg!(feature = "log")
+			{
+				true =>
+				{
+					input.parse::<Token![=]>()?;
+					let level = Level::parse(&input.parse()?)?;
+					set_once(&mut self.log, level, &name)
+				},
+				false => Err(syn::Error::new_spanned(&name, "`log` requires the `log` feature of `erro-rs`")),
+			},
+			"max_size" =>
+			{
+				input.parse::<Token![=]>()?;
+				let lit: LitInt = input.parse()?;
+				lit.base10_parse::<usize>()?;
+				set_once(&mut self.max_size, lit, &name)
+			},
+			"message" =>
+			{
+				let alias = match input.parse::<Option<Token![=]>>()?
+				{
+					Some(_) => Some(parse_ident(input)?),
+					None => None,
+				};
+
+				self.entries.push(Entry::message(&name, alias));
+				Ok(())
+			},
+			"module" =>
+			{
+				let module = match input.parse::<Option<Token![=]>>()?
+				{
+					Some(_) => Some(parse_ident(input)?),
+					None => None,
+				};
+
+				set_once(&mut self.module, module, &name)
+			},
+			"must_use" =>
+			{
+				let message = match input.parse::<Option<Token![=]>>()?
+				{
+					Some(_) => Some(input.parse()?),
+					None => None,
+				};
+
+				set_once(&mut self.must_use, message, &name)
+			},
+			"name" =>
+			{
+				input.parse::<Token![=]>()?;
+				let ident = parse_ident(input)?;
+				set_once(&mut self.name, ident, &name)
+			},
+			"naming" =>
+			{
+				input.parse::<Token![=]>()?;
+				let lit: LitStr = input.parse()?;
+				let naming = match lit.value().as_str()
+				{
+					"last" => Naming::Last,
+					"path" => Naming::Path,
+					_ => return Err(syn::Error::new_spanned(lit, "expected `naming = \"last\"` or `naming = \"path\"`")),
+				};
+
+				set_once(&mut self.naming, naming, &name)
+			},
+			"nested" => set_flag(&mut self.nested, &name),
+			"no_display" => set_flag(&mut self.no_display, &name),
+			"no_std" => set_flag(&mut self.no_std, &name),
+			"non_exhaustive" => set_flag(&mut self.non_exhaustive, &name),
+			"option" =>
+			{
+				let alias = match input.parse::<Option<Token![=]>>()?
+				{
+					Some(_) => Some(parse_ident(input)?),
+					None => None,
+				};
+
+				if self.entries.iter().any(|e| matches!(e.spec, Spec::Unit(_)))
+				{
+					return Err(syn::Error::new_spanned(&name, format!("`{}` is specified more than once", name)));
+				}
+
+				self.entries.push(Entry::unit(&name, alias));
+				Ok(())
+			},
+			"option_display" =>
+			{
+				input.parse::<Token![=]>()?;
+				let display = input.parse()?;
+				set_once(&mut self.option_display, display, &name)
+			},
+			"order" =>
+			{
+				input.parse::<Token![=]>()?;
+				let lit: LitStr = input.parse()?;
+				let order = match lit.value().as_str()
+				{
+					"sorted" => Order::Sorted,
+					"written" => Order::Written,
+					_ => return Err(syn::Error::new_spanned(lit, "expected `order = \"sorted\"` or `order = \"written\"`")),
+				};
+
+				set_once(&mut self.order, order, &name)
+			},
+			"other" =>
+			{
+				let alias = match input.parse::<Option<Token![=]>>()?
+				{
+					Some(_) => Some(parse_ident(input)?),
+					None => None,
+				};
+
+				set_flag(&mut self.other, &name)?;
+				self.entries.push(Entry::other(&name, alias));
+				Ok(())
+			},
+			"passthrough" => set_flag(&mut self.passthrough, &name),
+			"report_debug" => set_flag(&mut self.report_debug, &name),
+			"result_alias" =>
+			{
+				let alias = match input.parse::<Option<Token![=]>>()?
+				{
+					Some(_) => Some(parse_ident(input)?),
+					None => None,
+				};
+
+				set_once(&mut self.result_alias, alias, &name)
+			},
+			"send" => set_flag(&mut self.send, &name),
+			"send_sync" => set_flag(&mut self.send, &name).and_then(|_| set_flag(&mut self.sync, &name)),
+			"serialize" => match cfg!(feature = "serde")
+			{
+				true => set_flag(&mut self.serialize, &name),
+				false => Err(syn::Error::new_spanned(&name, "`serialize` requires the `serde` feature of `erro-rs`")),
+			},
+			"set" =>
+			{
+				input.parse::<Token![=]>()?;
+				if !input.peek(token::Bracket)
+				{
+					// Later sets are expanded after this one.
+					let set = input.call(Path::parse_mod_style)?;
+					self.set.get_or_insert(set);
+					return Ok(());
+				}
+
+				let content;
+				syn::bracketed!(content in input);
+				self.entries.extend(content.parse_terminated::<Entry, Token![,]>(Entry::parse)?.into_iter().map(|e| Entry {from_set: true, ..e}));
+				Ok(())
+			},
+			"share" =>
+			{
+				input.parse::<Token![=]>()?;
+				let share = parse_ident(input)?;
+				set_once(&mut self.share, share, &name)
+			},
+			"skip" => set_once(&mut self.skip, name.clone(), &name),
+			"strict" => set_flag(&mut self.strict, &name),
+			"sync" => set_flag(&mut self.sync, &name),
+			"tracing" => match cfg!(feature = "tracing")
+			{
+				true =>
+				{
+					input.parse::<Token![=]>()?;
+					let level = Level::parse(&input.parse()?)?;
+					set_once(&mut self.tracing, level, &name)
+				},
+				false => Err(syn::Error::new_spanned(&name, "`tracing` requires the `tracing` feature of `erro-rs`")),
+			},
+			"variant_prefix" =>
+			{
+				input.parse::<Token![=]>()?;
+				let prefix = input.parse()?;
+				set_once(&mut self.variant_prefix, prefix, &name)
+			},
+			"variant_suffix" =>
+			{
+				input.parse::<Token![=]>()?;
+				let suffix = input.parse()?;
+				set_once(&mut self.variant_suffix, suffix, &name)
+			},
+			"vis" =>
+			{
+				input.parse::<Token![=]>()?;
+				let lit: LitStr = input.parse()?;
+				let vis = lit.parse().map_err(|e| syn::Error::new_spanned(&lit, format!(
+					"{:?} is not a valid visibility: {}", lit.value(), e,
+				)))?;
+
+				set_once(&mut self.vis, vis, &name)
+			},
+			"wrap" =>
+			{
+				input.parse::<Token![=]>()?;
+				let wrap = Wrap::parse(&input.parse()?)?;
+				set_once(&mut self.wrap, wrap, &name)
+			},
+			_ => unreachable!("`{}` is in `OPTIONS` but cannot be parsed", name),
+		}
+	}
+}
+
+impl Parse for Args
+{
+	fn parse(input: ParseStream) -> syn::Result<Self>
+	{
+		let mut args = Self::default();
+		let mut count = 0;
+		let mut use_token = None;
+		while !input.is_empty()
+		{
+			count += 1;
+
+			// `use` is a keyword, so it is not found by `peek_option`.
+			if input.peek(Token![use])
+			{
+				use_token = Some(input.parse::<Token![use]>()?);
+				input.parse::<Token![=]>()?;
+				args.existing = Some(input.parse()?);
+			}
+			else if Self::peek_option(input)
+			{
+				args.parse_option(input)?;
+			}
+			else if let Some(ident) = Self::peek_misspelled(input)
+			{
+				return Err(syn::Error::new_spanned(&ident, format!(
+					"unknown option `{}`; expected an `Error` or one of: {} (an `Error` with a lowercase name must be written as a longer path, e.g. `self::{}`)",
+					ident,
+					OPTIONS.iter().map(|o| format!("`{}`", o)).collect::<Vec<_>>().join(", "),
+					ident,
+				)));
+			}
+			else
+			{
+				args.entries.push(input.parse()?);
+			}
+
+			if input.is_empty()
+			{
+				break;
+			}
+
+			if !input.peek(Token![,])
+			{
+				return Err(input.error("expected `,` after this argument; options and `Error`s are separated by commas"));
+			}
+
+			input.parse::<Token![,]>()?;
+		}
+
+		// An `Error` listed by a set is left out if it is listed elsewhere (or by an earlier set).
+		let written: Vec<_> = args.entries.iter().map(|e| crate::written(e.spec.source())).collect();
+		let duplicates: Vec<_> = args.entries.iter().enumerate().map(|(i, err)| err.from_set && args.entries.iter().enumerate()
+			.any(|(j, e)| j != i && (!e.from_set || j < i) && written[j] == written[i])
+		).collect();
+
+		let mut duplicates = duplicates.into_iter();
+		args.entries.retain(|_| !duplicates.next().unwrap_or_default());
+
+		if let Some(ref display) = args.option_display
+		{
+			if !args.entries.iter().any(|e| matches!(e.spec, Spec::Unit(_)))
+			{
+				return Err(syn::Error::new_spanned(display, "`option_display` requires `option`, whose variant it is the `Display` of"));
+			}
+		}
+
+		if args.log.is_none() && args.tracing.is_none()
+		{
+			if let Some(level) = args.entries.iter().find_map(|e| e.level.as_ref())
+			{
+				return Err(syn::Error::new_spanned(&level.lit, "`level` requires the `log` or `tracing` option"));
+			}
+		}
+
+		if !args.defmt
+		{
+			if let Some(err) = args.entries.iter().find(|e| e.defmt)
+			{
+				return Err(syn::Error::new_spanned(err.culprit(), "the `defmt` entry option requires the `defmt` option"));
+			}
+		}
+
+		if !args.diagnostic
+		{
+			if let Some(err) = args.entries.iter().find(|e| e.diagnostic_source || e.help.is_some())
+			{
+				let option = match err.help
+				{
+					Some(_) => "help",
+					None => "diagnostic_source",
+				};
+
+				return Err(syn::Error::new_spanned(err.culprit(), format!("`{}` requires the `diagnostic` option", option)));
+			}
+		}
+
+		// A function given nothing but the name of a shared `Error` returns the one generated by the
+		// function which lists its `Error`s.
+		if let (1, Some(share)) = (count, &args.share)
+		{
+			args.existing = Some(syn::parse_quote!(#share));
+		}
+
+		match (use_token, &args.skip)
+		{
+			(Some(use_token), _) if count > 1 => Err(syn::Error::new_spanned(use_token,
+				"`use` cannot be combined with any `Error`s or other options, since it replaces the generated `Error`",
+			)),
+			(_, Some(skip)) if count > 1 => Err(syn::Error::new_spanned(skip,
+				"`skip` cannot be combined with any `Error`s or other options, since the method is left as it is",
+			)),
+			_ => Ok(args),
+		}
+	}
+}
+
+/// What implements the traits of the generated `Error`, when given to the `backend` option.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum Backend
+{
+	/// `#[derive(thiserror::Error)]`, which implements [`Display`](std::fmt::Display),
+	/// [`Error`](std::error::Error), and [`From`] from `#[error]`, `#[from]`, and `#[source]`
+	/// attributes on each variant.
+	Thiserror,
+}
+
+/// How the `clone` option makes the generated `Error` `Clone`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum CloneBy
+{
+	/// By storing each `Error` in an [`Arc`](std::sync::Arc).
+	Arc,
+}
+
+/// The level of the events given to the `log` and `tracing` options, and the `level` entry option.
+#[derive(Clone, Debug)]
+pub(crate) struct Level
+{
+	/// The name of the macro which records an event at this level, e.g. `warn`.
+	pub(crate) ident: Ident,
+
+	/// Where the level was written.
+	pub(crate) lit: LitStr,
+}
+
+impl Level
+{
+	/// Parse the `lit`eral name of a level, e.g. `"warn"`.
+	pub(crate) fn parse(lit: &LitStr) -> syn::Result<Self>
+	{
+		match lit.value().as_str()
+		{
+			level @ ("error" | "warn" | "info" | "debug" | "trace") => Ok(Self {ident: Ident::new(level, lit.span()), lit: lit.clone()}),
+			_ => Err(syn::Error::new_spanned(lit, "expected one of `\"error\"`, `\"warn\"`, `\"info\"`, `\"debug\"`, or `\"trace\"`")),
+		}
+	}
+}
+
+/// How variant names are derived from the paths of each [`Entry`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub(crate) enum Naming
+{
+	/// Only the last segment of the path which contributes to the name, e.g. `std::io::Error`
+	/// maps to `Io`.
+	Last,
+
+	/// Every segment of the path, e.g. `std::io::Error` maps to `StdIo`.
+	#[default]
+	Path,
+}
+
+/// The order of the variants of the generated `Error`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub(crate) enum Order
+{
+	/// Sorted by their names.
+	Sorted,
+
+	/// The order in which their entries are written.
+	#[default]
+	Written,
+}
+
+/// Parse an identifier, or a string literal containing one, e.g. `ReadIntResult` or
+/// `"ReadIntResult"`.
+fn parse_ident(input: ParseStream) -> syn::Result<Ident>
+{
+	if input.peek(Ident)
+	{
+		return input.parse();
+	}
+
+	let lit: LitStr = input.parse()?;
+	syn::parse_str::<Ident>(&lit.value()).map(|mut ident|
+	{
+		ident.set_span(lit.span());
+		ident
+	}).map_err(|_| syn::Error::new_spanned(&lit, format!("expected a valid identifier, but {:?} is not", lit.value())))
+}
+
+/// Set the `flag`, unless it has already been set by the option `name`.
+pub(crate) fn set_flag(flag: &mut bool, name: &Ident) -> syn::Result<()>
+{
+	if *flag
+	{
+		return Err(syn::Error::new_spanned(name, format!("`{}` is specified more than once", name)));
+	}
+
+	*flag = true;
+	Ok(())
+}
+
+/// Set the `option` to `value`, unless it has already been set by the option `name`.
+pub(crate) fn set_once<T>(option: &mut Option<T>, value: T, name: &Ident) -> syn::Result<()>
+{
+	if option.is_some()
+	{
+		return Err(syn::Error::new_spanned(name, format!("`{}` is specified more than once", name)));
+	}
+
+	*option = Some(value);
+	Ok(())
+}
@@ -0,0 +1,82 @@
+use
+{
+	proc_macro2::{Group, Span, TokenStream as TokenStream2, TokenTree},
+	quote::{format_ident, quote},
+	syn::{parse::{Parse, ParseStream}, Ident, Path, Token},
+};
+
+/// A call of the `emit_errors!` macro, e.g. `emit_errors!(read_int)`, which emits the items that
+/// the `defer` option held back for the function at its `path`.
+pub(crate) struct Emit
+{
+	/// The path of the function whose items are emitted.
+	path: Path,
+}
+
+impl Emit
+{
+	/// The call of the `macro_rules!` which holds the items of the function.
+	pub(crate) fn expand(&self) -> TokenStream2
+	{
+		let mut path = self.path.clone();
+		if let Some(last) = path.segments.last_mut()
+		{
+			last.ident = name(&last.ident);
+		}
+
+		quote!(#path! {})
+	}
+}
+
+impl Parse for Emit
+{
+	fn parse(input: ParseStream) -> syn::Result<Self>
+	{
+		let path = input.parse()?;
+		input.parse::<Option<Token![;]>>()?;
+		Ok(Self {path})
+	}
+}
+
+/// The `macro_rules!` which holds the `items` generated for the `function`, until they are emitted
+/// by `emit_errors!`.
+pub(crate) fn define(function: &Ident, items: TokenStream2) -> TokenStream2
+{
+	let name = name(function);
+	quote!
+	{
+		#[allow(unused_macros)]
+		macro_rules! #name
+		{
+			() => { #items };
+		}
+
+		#[allow(unused_imports)]
+		pub(crate) use #name;
+	}
+}
+
+/// The `tokens`, reported at the `span`.
+pub(crate) fn respan(tokens: TokenStream2, span: Span) -> TokenStream2
+{
+	tokens.into_iter().map(|tt| match tt
+	{
+		TokenTree::Group(group) =>
+		{
+			let mut respanned = Group::new(group.delimiter(), respan(group.stream(), span));
+			respanned.set_span(span);
+			TokenTree::Group(respanned)
+		},
+		mut tt =>
+		{
+			tt.set_span(span);
+			tt
+		},
+	}).collect()
+}
+
+/// The name of the `macro_rules!` which holds the items generated for the `function`.
+fn name(function: &Ident) -> Ident
+{
+	format_ident!("{}_errors", function)
+}
@@ -0,0 +1,833 @@
+use
+{
+	std::fmt,
+
+	crate::{args::{set_flag, set_once, Args, Level, Naming, Order}, written},
+
+	heck::CamelCase,
+	proc_macro2::TokenStream as TokenStream2,
+	quote::{format_ident, quote, quote_spanned, ToTokens},
+	syn::{ext::IdentExt, parse::{Parse, ParseStream, Parser}, punctuated::Punctuated, spanned::Spanned, token, Attribute, Field, GenericArgument, Ident, Lit, LitInt, LitStr, Path, PathArguments, PathSegment, QSelf, Token, Type, TypePath},
+};
+
+/// The names of the options which can be passed to an [`Entry`], e.g. `weird_sys::Error(display = "debug")`.
+const OPTIONS: &[&str] = &["alias", "boxed", "cfg", "code", "defmt", "diagnostic_source", "display", "exit_code", "fields", "help", "level", "no_arc", "no_bound", "no_from", "opaque", "retryable", "severity", "status", "transparent"];
+
+/// The part of a variant name derived from `segment`, with a trailing "Error" removed if `strip`.
+fn segment_name(segment: &PathSegment, strip: bool) -> String
+{
+	let name = segment.ident.unraw().to_string();
+	match strip
+	{
+		true => name.strip_suffix("Error").unwrap_or(&name).to_camel_case(),
+		false => name.to_camel_case(),
+	}
+}
+
+/// The name of each variant which wraps one of the `entries`.
+///
+/// With `auto_disambiguate`, derived names which collide are lengthened until they no longer do.
+pub(crate) fn variants(entries: &[Entry], args: &Args) -> syn::Result<Vec<Ident>>
+{
+	let mut names = entries.iter().map(|e| e.name(args)).collect::<syn::Result<Vec<_>>>()?;
+
+	if args.auto_disambiguate
+	{
+		disambiguate(&mut names);
+	}
+
+	let prefix = args.variant_prefix.as_ref().map(LitStr::value).unwrap_or_default();
+	let suffix = args.variant_suffix.as_ref().map(LitStr::value).unwrap_or_default();
+	names.into_iter().map(|name| match name
+	{
+		Name::Alias(ident) => Ok(ident),
+		derived =>
+		{
+			let name = format!("{}{}{}", prefix, derived, suffix);
+			Ident::parse_any.parse_str(&name).map(|_| format_ident!("{}", name)).map_err(|_|
+			{
+				let message = format!("expected the variant names to be valid identifiers, but `{}` is not", name);
+				match args.variant_prefix.as_ref().or(args.variant_suffix.as_ref())
+				{
+					Some(lit) => syn::Error::new_spanned(lit, message),
+					None => syn::Error::new(proc_macro2::Span::call_site(), message),
+				}
+			})
+		},
+	}).collect()
+}
+
+/// Sort the `args`' entries by the names of their variants, if they should be.
+pub(crate) fn sort(args: &mut Args) -> syn::Result<()>
+{
+	if args.order.unwrap_or_default() == Order::Written
+	{
+		return Ok(());
+	}
+
+	let names = variants(&args.entries, args)?;
+	let mut sorted: Vec<_> = names.iter().map(Ident::to_string).zip(std::mem::take(&mut args.entries)).collect();
+	sorted.sort_by(|(a, _), (b, _)| a.cmp(b));
+	args.entries = sorted.into_iter().map(|(_, e)| e).collect();
+	Ok(())
+}
+
+/// Lengthen the shortest colliding derived `names` until none collide, or none of those which do
+/// can be lengthened. Collisions which cannot be resolved are left to be reported.
+///
+/// The generic arguments of a name are added before any more of its segments, since two names which
+/// differ only by those arguments cannot be told apart by their paths.
+fn disambiguate(names: &mut [Name])
+{
+	loop
+	{
+		let rendered: Vec<_> = names.iter().map(Name::to_string).collect();
+
+		// Only the colliding names which can still grow (and are shortest within their collision)
+		// are lengthened, from the first collision in which any of them can.
+		let growable = rendered.iter().enumerate()
+			.filter(|(i, name)| rendered[..*i].contains(name))
+			.map(|(_, collision)| names.iter().enumerate()
+				.filter(|(i, _)| rendered[*i] == *collision)
+				.filter_map(|(i, name)| match *name
+				{
+					Name::Derived {ref arguments, len, ref segments, with_arguments}
+						if len < segments.len() || !(with_arguments || arguments.is_empty()) => Some((i, len + usize::from(with_arguments))),
+					_ => None,
+				})
+				.collect::<Vec<_>>()
+			)
+			.find(|growable| !growable.is_empty());
+
+		let growable = match growable
+		{
+			Some(growable) => growable,
+			None => return,
+		};
+
+		let shortest = growable.iter().map(|(_, len)| *len).min().unwrap_or_default();
+		for (i, _) in growable.into_iter().filter(|(_, len)| *len == shortest)
+		{
+			if let Name::Derived {ref arguments, ref mut len, ref mut with_arguments, ..} = names[i]
+			{
+				match *with_arguments || arguments.is_empty()
+				{
+					true => *len += 1,
+					false => *with_arguments = true,
+				}
+			}
+		}
+	}
+}
+
+/// The name of a variant, before any disambiguation.
+enum Name
+{
+	/// The name is an alias given by the user.
+	Alias(Ident),
+
+	/// The name is derived from the last `len` of the `segments` of a path, followed by the names of
+	/// its generic `arguments` if `with_arguments`.
+	Derived
+	{
+		arguments: Vec<String>,
+		len: usize,
+		segments: Vec<String>,
+		with_arguments: bool,
+	},
+}
+
+impl fmt::Display for Name
+{
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result
+	{
+		match self
+		{
+			Self::Alias(ident) => write!(f, "{}", ident),
+			Self::Derived {arguments, len, segments, with_arguments} => segments[segments.len() - len..].iter()
+				.chain(arguments.iter().filter(|_| *with_arguments))
+				.try_for_each(|s| write!(f, "{}", s)),
+		}
+	}
+}
+
+/// An `Error` passed to the `#[errors]` macro, e.g. `std::io::Error = "Io"`.
+pub(crate) struct Entry
+{
+	/// The name given to the variant which wraps the `Error`, if any.
+	pub(crate) alias: Option<Lit>,
+
+	/// Whether the `Error` (or the fields of the variant) refer to the lifetimes of the function, so
+	/// that it cannot be the `source` of the generated `Error`.
+	pub(crate) borrowed: bool,
+
+	/// Whether the variant stores the `Error` in a [`Box`].
+	pub(crate) boxed: bool,
+
+	/// The predicate which the variant is conditionally compiled on, e.g. `feature = "postgres"`,
+	/// if any.
+	pub(crate) cfg: Option<TokenStream2>,
+
+	/// The numeric code of the variant, if it was given one.
+	pub(crate) code: Option<LitInt>,
+
+	/// Whether the `Error` implements `defmt::Format`, which the `defmt` option formats it with
+	/// (rather than its `Debug` implementation).
+	pub(crate) defmt: bool,
+
+	/// Whether the `Error` is a `miette::Diagnostic`, which the `diagnostic` option returns as the
+	/// `diagnostic_source` of the variant.
+	pub(crate) diagnostic_source: bool,
+
+	/// How the generated `Display` formats the `Error`.
+	pub(crate) display: Option<Display>,
+
+	/// Whether the `Error` is stored as it is, rather than in an [`Arc`](std::sync::Arc), when the
+	/// `clone` option is given (because it is `Clone` already).
+	pub(crate) no_arc: bool,
+
+	/// Whether the `Error` is deliberately not an [`Error`](std::error::Error), in which case it is
+	/// not asserted to be one, and the variant has no `source`.
+	pub(crate) no_bound: bool,
+
+	/// Whether to skip implementing [`From`] for the `Error`.
+	pub(crate) no_from: bool,
+
+	/// Whether the `Error` is not an [`Error`](std::error::Error) itself, but refers to one through
+	/// [`AsRef`] (e.g. `anyhow::Error`), which is its `source` instead.
+	pub(crate) opaque: bool,
+
+	/// The exit code of the process when it fails with this variant, if it was given one.
+	pub(crate) exit_code: Option<u8>,
+
+	/// The fields of the variant in addition to the `Error` being wrapped, if it has any.
+	pub(crate) fields: Option<Vec<Field>>,
+
+	/// Whether the `Error` was listed by a set given to the `set` option, in which case it is left out
+	/// if it is listed elsewhere too.
+	pub(crate) from_set: bool,
+
+	/// The generated `Error` which the variant was flattened from by the `flatten` option, if any.
+	pub(crate) flattened: Option<Path>,
+
+	/// Whether the `Error` (or the fields of the variant) refer to the generic parameters of the
+	/// function.
+	pub(crate) generic: bool,
+
+	/// The `help` of the variant when the `diagnostic` option is given, if any.
+	pub(crate) help: Option<LitStr>,
+
+	/// The level at which conversions into the variant are logged (or traced), if it is not that
+	/// of the `log` (or `tracing`) option.
+	pub(crate) level: Option<Level>,
+
+	/// Whether the operation which failed with the variant may succeed if it is tried again.
+	pub(crate) retryable: bool,
+
+	/// The severity of the variant, if it was given one.
+	pub(crate) severity: Option<Severity>,
+
+	/// The `Error` being wrapped.
+	pub(crate) spec: Spec,
+
+	/// The HTTP status code of the variant, if it was given one.
+	pub(crate) status: Option<u16>,
+
+	/// Whether the `Display` and `source` of the variant forward to the `Error` it wraps.
+	pub(crate) transparent: bool,
+}
+
+impl Entry
+{
+	/// An `Entry` for the `spec`, with none of its options given.
+	fn new(spec: Spec) -> Self
+	{
+		Self {alias: None, borrowed: false, boxed: false, cfg: None, code: None, defmt: false, diagnostic_source: false, display: None, exit_code: None, fields: None, flattened: None, from_set: false, generic: false, help: None, level: None, no_arc: false, no_bound: false, no_from: false, opaque: false, retryable: false, severity: None, spec, status: None, transparent: false}
+	}
+
+	/// The catch-all `Entry` added by the `other` option `name`, which wraps any boxed `Error`.
+	pub(crate) fn other(name: &Ident, alias: Option<Ident>) -> Self
+	{
+		let alias = alias.map_or_else(|| LitStr::new("Other", name.span()), |a| LitStr::new(&a.to_string(), a.span()));
+		let lit = LitStr::new("Box<dyn std::error::Error + Send + Sync>", name.span());
+		let ty = lit.parse().expect("the catch-all `Error` should be a valid type");
+
+		Self {alias: Some(Lit::Str(alias)), ..Self::new(Spec::Str(lit, Box::new(ty)))}
+	}
+
+	/// A message `Entry` added by the `message` option `name`, which wraps a [`String`].
+	pub(crate) fn message(name: &Ident, alias: Option<Ident>) -> Self
+	{
+		let alias = alias.map_or_else(|| LitStr::new("Message", name.span()), |a| LitStr::new(&a.to_string(), a.span()));
+		Self {alias: Some(Lit::Str(alias)), ..Self::new(Spec::Message(name.clone()))}
+	}
+
+	/// The `Entry` added by the `option` option `name`, whose variant wraps nothing and is returned
+	/// in place of a missing value.
+	pub(crate) fn unit(name: &Ident, alias: Option<Ident>) -> Self
+	{
+		let alias = alias.map_or_else(|| LitStr::new("NotFound", name.span()), |a| LitStr::new(&a.to_string(), a.span()));
+		Self {alias: Some(Lit::Str(alias)), ..Self::new(Spec::Unit(name.clone()))}
+	}
+
+	/// The `Entry` for the `variant` of the generated `Error` at `inner`, which wraps a `ty`, added
+	/// by the `flatten` option.
+	pub(crate) fn flattened(inner: &Path, variant: &Ident, ty: Type) -> syn::Result<Self>
+	{
+		let spec = match ty
+		{
+			Type::Path(ty) if ty.qself.is_none() => Spec::Path(ty.path),
+			Type::Path(ty) => Spec::Qualified(ty),
+			ty => return Err(syn::Error::new_spanned(ty, "expected the path of an `Error`, e.g. `std::io::Error`")),
+		};
+
+		let alias = LitStr::new(&variant.to_string(), variant.span());
+		Ok(Self {alias: Some(Lit::Str(alias)), flattened: Some(inner.clone()), ..Self::new(spec)})
+	}
+
+	/// The `Entry` for an `Error` which was found in the function rather than listed, by the
+	/// `infer` or `passthrough` options.
+	pub(crate) fn found(ty: Type) -> Self
+	{
+		let spec = match ty
+		{
+			Type::Path(ty) if ty.qself.is_some() => Spec::Qualified(ty),
+			Type::Path(ty) if ty.path.segments.iter().all(|s| s.arguments.is_empty()) => Spec::Path(ty.path),
+			ty => Spec::Str(LitStr::new(&written(&ty), ty.span()), Box::new(ty)),
+		};
+
+		Self::new(spec)
+	}
+
+	/// Whether [`From`] is implemented for the `Error`, which it cannot be if the variant has
+	/// additional fields to fill in, or if the `Error` is generic or a qualified path (and so might
+	/// be any type, as far as coherence is concerned, including the generated `Error` itself), or if
+	/// there is no `Error` at all.
+	pub(crate) fn implements_from(&self) -> bool
+	{
+		!self.no_from && !self.generic && self.fields.is_none() && !matches!(self.spec, Spec::Qualified(_) | Spec::Unit(_))
+	}
+
+	/// The `#[cfg]` attribute which conditionally compiles the variant for this `Entry` (and every
+	/// item which refers to it), if any.
+	pub(crate) fn cfg_attr(&self) -> Option<TokenStream2>
+	{
+		self.cfg.as_ref().map(|predicate| quote!(#[cfg(#predicate)]))
+	}
+
+	/// The tokens which should be blamed when the variant for this `Entry` is named incorrectly.
+	pub(crate) fn culprit(&self) -> TokenStream2
+	{
+		match self.alias
+		{
+			Some(ref alias) => alias.to_token_stream(),
+			None => self.spec.source(),
+		}
+	}
+
+	/// The name of the variant which wraps this `Entry`, before any disambiguation.
+	fn name(&self, args: &Args) -> syn::Result<Name>
+	{
+		let path = match self.alias
+		{
+			Some(Lit::Str(ref alias)) => return syn::parse_str::<Ident>(&alias.value()).map(|mut ident|
+			{
+				ident.set_span(alias.span());
+				Name::Alias(ident)
+			}).map_err(|_| syn::Error::new_spanned(alias, format!(
+				"alias must be a valid identifier, but {:?} is not", alias.value(),
+			))),
+			Some(ref alias) => return Err(syn::Error::new_spanned(alias, "alias must be an identifier or string literal, e.g. `Io`")),
+			None => match self.spec
+			{
+				Spec::Path(ref path) => Some(path),
+				Spec::Qualified(ref ty) => return Err(syn::Error::new_spanned(ty, format!(
+					"qualified paths must be given an alias, e.g. `{} = \"...\"`", written(ty),
+				))),
+				Spec::Str(ref lit, _) if self.spec.is_dyn() => return Err(syn::Error::new_spanned(lit, format!(
+					"trait objects must be given an alias, e.g. `{} = \"...\"`", written(lit),
+				))),
+				Spec::Str(_, ref ty) => match **ty
+				{
+					Type::Path(ref ty) if ty.qself.is_none() => Some(&ty.path),
+					_ => None,
+				},
+				Spec::Message(_) | Spec::Unit(_) => None,
+			},
+		};
+
+		let segments: Vec<_> = path.into_iter().flat_map(|p| &p.segments)
+			.filter(|s| !matches!(s.ident.to_string().as_str(), "crate" | "self" | "super"))
+			.collect();
+
+		let last = segments.len().saturating_sub(1);
+		let names: Vec<_> = segments.into_iter().enumerate()
+			.map(|(i, s)| segment_name(s, i == last && !args.keep_suffix))
+			.filter(|n| !n.is_empty())
+			.collect();
+
+		if names.is_empty()
+		{
+			let source = self.spec.source();
+			return Err(syn::Error::new_spanned(&source, format!(
+				"no variant name can be derived from `{}`; give it an alias, e.g. `{} = \"...\"`",
+				written(&source), written(&source),
+			)));
+		}
+
+		let len = match (&self.spec, args.naming.unwrap_or_default())
+		{
+			(Spec::Path(_), Naming::Path) => names.len(),
+			_ => 1,
+		};
+
+		let arguments = path.and_then(|p| p.segments.last()).into_iter()
+			.flat_map(|s| match s.arguments
+			{
+				PathArguments::AngleBracketed(ref args) => args.args.iter().collect(),
+				_ => Vec::new(),
+			})
+			.filter_map(|arg| match *arg
+			{
+				GenericArgument::Type(Type::Path(ref ty)) if ty.qself.is_none() => ty.path.segments.last(),
+				_ => None,
+			})
+			.map(|s| segment_name(s, false))
+			.collect();
+
+		Ok(Name::Derived {arguments, len, segments: names, with_arguments: false})
+	}
+}
+
+impl Entry
+{
+	/// Parse the next option of this `Entry` in the `input`.
+	fn parse_option(&mut self, input: ParseStream) -> syn::Result<()>
+	{
+		let name: Ident = input.call(Ident::parse_any)?;
+		match name.to_string().as_str()
+		{
+			"alias" =>
+			{
+				equals(input, &name)?;
+				let alias = parse_alias(input)?;
+				set_once(&mut self.alias, alias, &name)
+			},
+			"boxed" => set_flag(&mut self.boxed, &name),
+			"cfg" =>
+			{
+				let content;
+				syn::parenthesized!(content in input);
+				let predicate = content.parse()?;
+				set_once(&mut self.cfg, predicate, &name)
+			},
+			"code" =>
+			{
+				equals(input, &name)?;
+				let code: LitInt = input.parse()?;
+				code.base10_parse::<u32>()?;
+				set_once(&mut self.code, code, &name)
+			},
+			"defmt" => set_flag(&mut self.defmt, &name),
+			"diagnostic_source" => set_flag(&mut self.diagnostic_source, &name),
+			"display" =>
+			{
+				equals(input, &name)?;
+				let lit: LitStr = input.parse()?;
+				let display = match lit.value().as_str()
+				{
+					"debug" => Display::Debug,
+					"display" => Display::Display,
+					_ => return Err(syn::Error::new_spanned(lit, "expected `display = \"debug\"` or `display = \"display\"`")),
+				};
+
+				if self.transparent
+				{
+					return Err(syn::Error::new_spanned(&name, "`display` cannot be combined with `transparent`"));
+				}
+
+				set_once(&mut self.display, display, &name)
+			},
+			"exit_code" =>
+			{
+				equals(input, &name)?;
+				let lit: LitInt = input.parse()?;
+				let exit_code = lit.base10_parse::<u8>().map_err(|_| syn::Error::new_spanned(&lit, format!(
+					"exit codes must be within `0..=255`, but {} is not", lit,
+				)))?;
+
+				set_once(&mut self.exit_code, exit_code, &name)
+			},
+			"fields" =>
+			{
+				let content;
+				syn::parenthesized!(content in input);
+
+				let mut fields = Vec::new();
+				while !content.is_empty()
+				{
+					let attrs = content.call(Attribute::parse_outer)?;
+					fields.push(Field {attrs, ..content.call(Field::parse_named)?});
+					if content.is_empty()
+					{
+						break;
+					}
+
+					content.parse::<Token![,]>()?;
+				}
+
+				if fields.is_empty()
+				{
+					return Err(syn::Error::new_spanned(&name, "expected at least one field, e.g. `fields(path: std::path::PathBuf)`"));
+				}
+
+				set_once(&mut self.fields, fields, &name)
+			},
+			"help" =>
+			{
+				equals(input, &name)?;
+				let help = input.parse()?;
+				set_once(&mut self.help, help, &name)
+			},
+			"level" =>
+			{
+				equals(input, &name)?;
+				let level = Level::parse(&input.parse()?)?;
+				set_once(&mut self.level, level, &name)
+			},
+			"no_arc" => set_flag(&mut self.no_arc, &name),
+			"no_bound" =>
+			{
+				if self.transparent
+				{
+					return Err(syn::Error::new_spanned(&name, "`no_bound` cannot be combined with `transparent`"));
+				}
+
+				if self.opaque
+				{
+					return Err(syn::Error::new_spanned(&name, "`no_bound` cannot be combined with `opaque`"));
+				}
+
+				set_flag(&mut self.no_bound, &name)
+			},
+			"no_from" => set_flag(&mut self.no_from, &name),
+			"opaque" =>
+			{
+				if self.no_bound
+				{
+					return Err(syn::Error::new_spanned(&name, "`opaque` cannot be combined with `no_bound`"));
+				}
+
+				set_flag(&mut self.opaque, &name)
+			},
+			"retryable" => set_flag(&mut self.retryable, &name),
+			"severity" =>
+			{
+				equals(input, &name)?;
+				let lit: LitStr = input.parse()?;
+				let severity = match lit.value().as_str()
+				{
+					"critical" => Severity::Critical,
+					"error" => Severity::Error,
+					"warn" => Severity::Warn,
+					_ => return Err(syn::Error::new_spanned(lit, "expected `severity = \"warn\"`, `severity = \"error\"`, or `severity = \"critical\"`")),
+				};
+
+				set_once(&mut self.severity, severity, &name)
+			},
+			"status" =>
+			{
+				equals(input, &name)?;
+				let lit: LitInt = input.parse()?;
+				let status = lit.base10_parse::<u16>().ok()
+					.filter(|s| (100..=599).contains(s))
+					.ok_or_else(|| syn::Error::new_spanned(&lit, format!(
+						"HTTP status codes must be within `100..=599`, but {} is not", lit,
+					)))?;
+
+				set_once(&mut self.status, status, &name)
+			},
+			"transparent" =>
+			{
+				if self.display.is_some()
+				{
+					return Err(syn::Error::new_spanned(&name, "`transparent` cannot be combined with `display`"));
+				}
+
+				if self.no_bound
+				{
+					return Err(syn::Error::new_spanned(&name, "`transparent` cannot be combined with `no_bound`"));
+				}
+
+				set_flag(&mut self.transparent, &name)
+			},
+			_ => Err(syn::Error::new_spanned(&name, format!(
+				"unknown option `{}`; expected one of: {}",
+				name,
+				OPTIONS.iter().map(|o| format!("`{}`", o)).collect::<Vec<_>>().join(", "),
+			))),
+		}
+	}
+}
+
+impl Parse for Entry
+{
+	fn parse(input: ParseStream) -> syn::Result<Self>
+	{
+		let mut entry = Self::new(input.parse()?);
+
+		if input.peek(token::Paren)
+		{
+			let content;
+			syn::parenthesized!(content in input);
+			while !content.is_empty()
+			{
+				entry.parse_option(&content)?;
+				if content.is_empty()
+				{
+					break;
+				}
+
+				if content.peek(Token![=])
+				{
+					return Err(content.error("this option does not take a value"));
+				}
+
+				content.parse::<Token![,]>()?;
+			}
+		}
+
+		if input.parse::<Option<Token![=]>>()?.is_some() || input.parse::<Option<Token![as]>>()?.is_some()
+		{
+			let alias = parse_alias(input)?;
+			if entry.alias.is_some()
+			{
+				return Err(syn::Error::new_spanned(alias, "`alias` is specified more than once"));
+			}
+
+			entry.alias = Some(alias);
+		}
+
+		Ok(entry)
+	}
+}
+
+/// Parse the alias of an `Entry`, which is an identifier (e.g. `Io` or `r#type`) or a string
+/// containing one (e.g. `"Io"`).
+fn parse_alias(input: ParseStream) -> syn::Result<Lit>
+{
+	if input.peek(Ident::peek_any)
+	{
+		// Keywords are reported as invalid identifiers when the variant is named.
+		let ident = input.call(Ident::parse_any)?;
+		return Ok(Lit::Str(LitStr::new(&ident.to_string(), ident.span())));
+	}
+
+	input.parse()
+}
+
+/// Parse the `=` between the option `name` and its value in the `input`.
+fn equals(input: ParseStream, name: &Ident) -> syn::Result<()>
+{
+	match input.parse::<Option<Token![=]>>()?
+	{
+		Some(_) => Ok(()),
+		None => Err(syn::Error::new_spanned(name, format!("`{}` requires a value, e.g. `{} = ...`", name, name))),
+	}
+}
+
+/// How the generated `Display` formats the `Error` wrapped by a variant.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub(crate) enum Display
+{
+	/// With its [`Debug`](std::fmt::Debug) implementation.
+	Debug,
+
+	/// With its [`Display`](std::fmt::Display) implementation.
+	#[default]
+	Display,
+}
+
+/// How severe a variant is, as reported by the generated `severity` method.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub(crate) enum Severity
+{
+	/// The variant is fatal to the program.
+	Critical,
+
+	/// The variant is an ordinary failure.
+	#[default]
+	Error,
+
+	/// The variant may be recovered from.
+	Warn,
+}
+
+impl Severity
+{
+	/// The name of this severity, e.g. `"warn"`.
+	pub(crate) fn as_str(self) -> &'static str
+	{
+		match self
+		{
+			Self::Critical => "critical",
+			Self::Error => "error",
+			Self::Warn => "warn",
+		}
+	}
+
+	/// The name of the macro which logs (or traces) an event at this severity, e.g. `warn`.
+	pub(crate) fn level(self) -> &'static str
+	{
+		match self
+		{
+			Self::Critical | Self::Error => "error",
+			Self::Warn => "warn",
+		}
+	}
+}
+
+/// Parse a qualified path, e.g. `<T as std::str::FromStr>::Err`.
+///
+/// [`TypePath`]'s own `Parse` would take the options of the `Entry` which follow it as the
+/// parenthesized arguments of its last segment (as in `Fn(A)`), so the segments after the `>` are
+/// parsed in the module style instead.
+fn parse_qualified(input: ParseStream) -> syn::Result<TypePath>
+{
+	let lt_token = input.parse()?;
+	let ty = input.parse()?;
+	let (as_token, mut path) = match input.parse::<Option<Token![as]>>()?
+	{
+		// Nothing but the `>` can follow the trait, so it may have generic arguments.
+		Some(as_token) => (Some(as_token), input.parse()?),
+		None => (None, Path {leading_colon: None, segments: Punctuated::new()}),
+	};
+
+	let gt_token = input.parse()?;
+	let colon2_token = input.parse()?;
+	let rest = input.call(Path::parse_mod_style)?;
+
+	// Like `syn`, the `::` after the `>` separates the trait from the rest of the path (if there is
+	// a trait).
+	let position = path.segments.len();
+	match position
+	{
+		0 => path.leading_colon = Some(colon2_token),
+		_ => path.segments.push_punct(colon2_token),
+	}
+
+	path.segments.extend(rest.segments.into_pairs());
+	Ok(TypePath {qself: Some(QSelf {lt_token, ty, position, as_token, gt_token}), path})
+}
+
+/// The way an `Error` was written in the `#[errors]` macro.
+pub(crate) enum Spec
+{
+	/// A message, added by the `message` option (whose name is kept for diagnostics).
+	Message(Ident),
+
+	/// A path to the `Error`, e.g. `std::io::Error`.
+	Path(Path),
+
+	/// A qualified path to the `Error`, e.g. `<T as std::str::FromStr>::Err`.
+	Qualified(TypePath),
+
+	/// A string containing the type of the `Error`, e.g. `"std::sync::mpsc::SendError<String>"`.
+	Str(LitStr, Box<Type>),
+
+	/// No `Error` at all, added by the `option` option (whose name is kept for diagnostics).
+	Unit(Ident),
+}
+
+impl Spec
+{
+	/// The tokens which were written to specify the `Error`.
+	pub(crate) fn source(&self) -> TokenStream2
+	{
+		match self
+		{
+			Self::Message(name) => name.to_token_stream(),
+			Self::Path(path) => path.to_token_stream(),
+			Self::Qualified(ty) => ty.to_token_stream(),
+			Self::Str(lit, _) => lit.to_token_stream(),
+			Self::Unit(name) => name.to_token_stream(),
+		}
+	}
+
+	/// Whether this `Spec` is a trait object, either bare (`dyn Error`) or behind a pointer
+	/// (`Box<dyn Error>`).
+	pub(crate) fn is_dyn(&self) -> bool
+	{
+		let ty = match self
+		{
+			Self::Message(_) | Self::Path(_) | Self::Qualified(_) | Self::Unit(_) => return false,
+			Self::Str(_, ty) => &**ty,
+		};
+
+		match ty
+		{
+			Type::TraitObject(_) => true,
+			Type::Path(ty) => ty.path.segments.last().is_some_and(|last| match last.arguments
+			{
+				PathArguments::AngleBracketed(ref args) => args.args.iter().any(|arg|
+					matches!(arg, GenericArgument::Type(Type::TraitObject(_)))
+				),
+				_ => false,
+			}),
+			_ => false,
+		}
+	}
+}
+
+impl Parse for Spec
+{
+	fn parse(input: ParseStream) -> syn::Result<Self>
+	{
+		if input.peek(LitStr)
+		{
+			let lit: LitStr = input.parse()?;
+			let ty = lit.parse().map_err(|e| syn::Error::new_spanned(&lit, format!(
+				"{:?} is not a valid type: {}", lit.value(), e,
+			)))?;
+			return Ok(Self::Str(lit, Box::new(ty)));
+		}
+
+		if input.peek(Token![<])
+		{
+			return input.call(parse_qualified).map(Self::Qualified);
+		}
+
+		if !input.peek(Ident::peek_any) && !input.peek(Token![::])
+		{
+			return Err(input.error(
+				"expected the path of an `Error` (e.g. `std::io::Error`), a string containing its type (e.g. `\"Box<dyn Error>\"`), or an option",
+			));
+		}
+
+		let path = input.call(Path::parse_mod_style)?;
+		if input.peek(Token![<])
+		{
+			return Err(input.error(format!(
+				"generic arguments cannot be written after a path; write the `Error` as a string instead, e.g. `\"{}<...>\"`",
+				written(&path),
+			)));
+		}
+
+		Ok(Self::Path(path))
+	}
+}
+
+impl ToTokens for Spec
+{
+	fn to_tokens(&self, tokens: &mut TokenStream2)
+	{
+		match self
+		{
+			Self::Message(name) => tokens.extend(quote_spanned!(name.span()=> std::string::String)),
+			Self::Path(path) => path.to_tokens(tokens),
+			Self::Qualified(ty) => ty.to_tokens(tokens),
+			Self::Str(_, ty) => ty.to_tokens(tokens),
+			Self::Unit(name) => tokens.extend(quote_spanned!(name.span()=> ())),
+		}
+	}
+}
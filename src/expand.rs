@@ -0,0 +1,2358 @@
+use
+{
+	crate::{args::{Args, Backend, CloneBy, Level}, entry::{self, Display, Entry, Spec}, generics, written},
+
+	heck::SnakeCase,
+	proc_macro2::{Group, Spacing, TokenStream as TokenStream2, TokenTree},
+	quote::{format_ident, quote, quote_spanned, ToTokens},
+	syn::{ext::IdentExt, spanned::Spanned, Generics, Ident, LitStr, Visibility},
+};
+
+/// An `Error` generated by the `#[errors]` macro.
+pub(crate) struct ErrorEnum<'a>
+{
+	/// The arguments which were passed to the macro.
+	pub(crate) args: &'a Args,
+
+	/// The numeric code of each variant, if they have codes.
+	pub(crate) codes: Option<Vec<u32>>,
+
+	/// The path of the function which returns this `Error`, as it is referred to in documentation
+	/// (e.g. `load`, or `Config::load` for a method).
+	pub(crate) function: String,
+
+	/// The generic parameters of the function which this `Error` refers to.
+	pub(crate) generics: Generics,
+
+	/// The name of this `Error`.
+	pub(crate) ident: Ident,
+
+	/// The module which this `Error` is declared in, and the visibility it is re-exported with, if
+	/// it is not declared beside the function.
+	pub(crate) module: Option<(Ident, Visibility)>,
+
+	/// The name of each variant, in the same order as the `args`' entries.
+	pub(crate) variants: Vec<Ident>,
+
+	/// The visibility of this `Error`.
+	pub(crate) vis: Visibility,
+}
+
+impl<'a> ErrorEnum<'a>
+{
+	/// Validate the `args` and name the variants of the `Error` which they describe.
+	pub(crate) fn new(args: &'a Args, function: String, generics: Generics, ident: Ident, vis: Visibility) -> syn::Result<Self>
+	{
+		check_duplicates(&args.entries)?;
+		let variants = entry::variants(&args.entries, args)?;
+		check_collisions(&args.entries, &variants)?;
+		if args.backtrace || args.helpers || args.location || args.entries.iter().any(|e| e.fields.is_some())
+		{
+			check_method_collisions(&args.entries, &variants)?;
+		}
+
+		if let Some(name) = args.entries.iter().find_map(|e| match e.spec
+		{
+			Spec::Unit(ref name) => Some(name),
+			_ => None,
+		})
+		{
+			let conflict = [(args.backtrace, "backtrace"), (args.location, "location"), (!args.capture.is_empty(), "capture"), (!args.into.is_empty(), "into")]
+				.iter()
+				.find_map(|&(enabled, option)| enabled.then_some(option));
+
+			if let Some(option) = conflict
+			{
+				return Err(syn::Error::new_spanned(name, format!(
+					"`{}` cannot be combined with `{}`, since its variant has no `Error` or fields", name, option,
+				)));
+			}
+		}
+
+		if args.clone.is_some()
+		{
+			// A `Backtrace` is not `Clone`, and an `Error` shared by an `Arc` cannot be moved out of it.
+			if let Some(option) = [(args.backtrace, "backtrace"), (!args.into.is_empty(), "into")].iter().find_map(|&(enabled, option)| enabled.then_some(option))
+			{
+				return Err(syn::Error::new(proc_macro2::Span::call_site(), format!("`clone` cannot be combined with `{}`", option)));
+			}
+		}
+
+		if args.backend == Some(Backend::Thiserror)
+		{
+			check_thiserror(args)?;
+		}
+
+		// Only the lifetimes of the `Error` cannot change its size.
+		if let (Some(max_size), true) = (&args.max_size, generics.type_params().next().is_some() || generics.const_params().next().is_some())
+		{
+			return Err(syn::Error::new_spanned(max_size, "`max_size` cannot be used with `Error`s which refer to the generic types or constants of the function, since their size is not known"));
+		}
+
+		if args.no_display
+		{
+			check_no_display(args)?;
+		}
+
+		if args.no_std
+		{
+			check_no_std(args)?;
+		}
+
+		let codes = codes(args)?;
+		let vis = args.vis.clone().unwrap_or(vis);
+		let (module, vis) = match args.module
+		{
+			Some(ref module) =>
+			{
+				let module = module.clone().unwrap_or_else(|| format_ident!("{}", ident.to_string().to_snake_case()));
+				let inner = within(&vis);
+				(Some((module, vis)), inner)
+			},
+			None => (None, vis),
+		};
+
+		Ok(Self {args, codes, function, generics, ident, module, variants, vis})
+	}
+
+	/// The `items` generated for this `Error`, declared in its module if it has one.
+	pub(crate) fn declare(&self, items: TokenStream2) -> TokenStream2
+	{
+		match self.module
+		{
+			Some((ref module, ref vis)) => quote!
+			{
+				mod #module
+				{
+					#[allow(unused_imports)]
+					use super::*;
+
+					#items
+				}
+
+				#vis use #module::*;
+			},
+			None => items,
+		}
+	}
+
+	/// The entries of this `Error` alongside the names of their variants.
+	fn entries(&self) -> impl Iterator<Item = (&Entry, &Ident)> + Clone
+	{
+		self.args.entries.iter().zip(&self.variants)
+	}
+
+	/// This `Error` as a type, e.g. `ParseAllError<T>`.
+	pub(crate) fn ty(&self) -> TokenStream2
+	{
+		let ident = &self.ident;
+		let (_, ty_generics, _) = self.generics.split_for_impl();
+		quote!(#ident #ty_generics)
+	}
+
+	/// The start of an `impl` block for this `Error` (of the `trait_path`, if any), with the
+	/// additional where-clause `bounds`.
+	fn impl_header(&self, trait_path: Option<TokenStream2>, bounds: &[TokenStream2]) -> TokenStream2
+	{
+		let (impl_generics, _, where_clause) = self.generics.split_for_impl();
+		let trait_path = trait_path.map(|t| quote!(#t for));
+		let ty = self.ty();
+		let predicates = where_clause.iter().flat_map(|w| &w.predicates);
+		let where_clause = (where_clause.is_some() || !bounds.is_empty()).then(|| quote!(where #(#predicates,)* #(#bounds,)*));
+		quote!(impl #impl_generics #trait_path #ty #where_clause)
+	}
+
+	/// The bounds which this `Error` must satisfy to implement [`Error`](std::error::Error), if it
+	/// is generic.
+	fn error_bounds(&self) -> Vec<TokenStream2>
+	{
+		let mut bounds: Vec<_> = self.args.entries.iter().filter(|e| e.generic).map(|e|
+		{
+			let spec = &e.spec;
+			match (e.no_bound, e.opaque)
+			{
+				(true, _) => quote!(#spec: core::fmt::Display),
+				(false, true) =>
+				{
+					let opaque = opaque();
+					quote!(#spec: core::fmt::Display + core::convert::AsRef<#opaque>)
+				},
+				(false, false) => quote!(#spec: std::error::Error + 'static),
+			}
+		}).collect();
+
+		// The derived `Debug` may require more of the parameters than the `Error`s do.
+		if !self.generics.params.is_empty() && !self.args.report_debug
+		{
+			bounds.push(quote!(Self: core::fmt::Debug));
+		}
+
+		bounds
+	}
+
+	/// How the function which returns this `Error` is referred to in messages, without the `r#` of
+	/// any raw identifiers (e.g. `try` for `r#try`).
+	fn function_name(&self) -> String
+	{
+		self.function.replace("r#", "")
+	}
+
+	/// How each `Error` is referred to in documentation.
+	pub(crate) fn entry_docs(&self) -> Vec<String>
+	{
+		self.args.entries.iter().map(|e| match e.spec
+		{
+			Spec::Message(_) => "a message".into(),
+			Spec::Path(ref path) => format!("[`{}`]", written(path)),
+			Spec::Qualified(ref ty) => format!("`{}`", written(ty)),
+			Spec::Str(ref lit, _) => format!("`{}`", lit.value()),
+			Spec::Unit(_) => "a missing value".into(),
+		}).collect()
+	}
+
+	/// Generate this `Error` and the items which accompany it.
+	pub(crate) fn expand(&self) -> TokenStream2
+	{
+		let definition = self.definition();
+		let assertions = self.assertions();
+		let thread_safety = self.thread_safety();
+		let max_size = self.max_size();
+		let thiserror = self.args.backend == Some(Backend::Thiserror);
+		let display = (!thiserror && !self.args.no_display).then(|| self.display());
+		let conversions = self.conversions();
+		let outer_conversions = self.outer_conversions();
+		let io_conversion = self.args.into_io.then(|| self.io_conversion());
+		let dyn_impls = self.args.dyn_impls.then(|| self.dyn_impls());
+		let flattened_conversions = self.flattened_conversions();
+		let constructors = self.constructors();
+		let context = self.context();
+		let option = self.option();
+		let error_impl = (!thiserror).then(|| self.error_impl());
+		let report_debug = self.args.report_debug.then(|| self.report_debug());
+		let backtrace = self.args.backtrace.then(|| self.backtrace());
+		let location = self.args.location.then(|| self.location());
+		let captures = (!self.args.capture.is_empty()).then(|| self.captures());
+		let helpers = self.args.helpers.then(|| self.helpers());
+		let kind = (self.args.kind || self.args.eq_kind).then(|| self.kind());
+		let code = self.code();
+		let exit_code = self.exit_code();
+		let retryable = self.retryable();
+		let severity = self.severity();
+		let status_code = self.status_code();
+		let serialize = self.args.serialize.then(|| self.serialize());
+		let diagnostic = self.args.diagnostic.then(|| self.diagnostic());
+		let into_response = self.args.into_response.then(|| self.response());
+		let defmt = self.args.defmt.then(|| self.defmt());
+
+		quote!
+		{
+			#definition
+			#assertions
+			#thread_safety
+			#max_size
+			#display
+			#(#conversions)*
+			#(#outer_conversions)*
+			#io_conversion
+			#dyn_impls
+			#(#flattened_conversions)*
+			#constructors
+			#context
+			#option
+			#error_impl
+			#report_debug
+			#backtrace
+			#location
+			#captures
+			#helpers
+			#kind
+			#code
+			#exit_code
+			#retryable
+			#severity
+			#status_code
+			#serialize
+			#diagnostic
+			#into_response
+			#defmt
+		}
+	}
+
+	/// The definition of the `enum`.
+	fn definition(&self) -> TokenStream2
+	{
+		let Self {args, ident, vis, ..} = self;
+
+		let doc = match args.doc
+		{
+			Some(ref doc) => doc.value().lines().map(str::to_owned).collect(),
+			None => vec![format!("The [error](std::error::Error) returned by [`{}`]", self.function)],
+		};
+
+		let thiserror = (args.backend == Some(Backend::Thiserror)).then(|| quote!(thiserror::Error));
+		let clone = (args.clone.is_some() && !args.derives.iter().any(|d| d.segments.last().is_some_and(|s| s.ident == "Clone")))
+			.then(|| quote!(Clone));
+		let derives: Vec<_> = (!args.report_debug).then(|| quote!(Debug)).into_iter()
+			.chain(thiserror.clone())
+			.chain(clone)
+			.chain(args.derives.iter().map(ToTokens::to_token_stream))
+			.collect();
+		let derives = (!derives.is_empty()).then(|| quote!(#[derive(#(#derives),*)]));
+		let enum_attrs = &args.enum_attrs;
+		let must_use = args.must_use.as_ref().map(|message| match message
+		{
+			Some(message) => quote!(#[must_use = #message]),
+			None => quote!(#[must_use]),
+		});
+
+		let non_exhaustive = args.non_exhaustive.then(|| quote!(#[non_exhaustive]));
+		let variant_docs = self.entries().zip(self.entry_docs()).map(|((e, variant), ty)| match e.alias
+		{
+			_ if matches!(e.spec, Spec::Message(_)) => "Returned with a message.".into(),
+			_ if matches!(e.spec, Spec::Unit(_)) => "Returned in place of a missing value.".into(),
+			Some(_) => format!("Returned when {} occurs (aliased as `{}`).", ty, variant),
+			None => format!("Returned when {} occurs.", ty),
+		});
+
+		let extras = self.extras();
+		let variants = self.entries().enumerate().map(|(i, (err, variant))|
+		{
+			let cfg = err.cfg_attr();
+			let (attrs, source_attr) = thiserror.as_ref().map(|_| self.thiserror_attrs(i, err)).unzip();
+			if let Spec::Unit(_) = err.spec
+			{
+				return quote!(#cfg #attrs #variant);
+			}
+
+			let spec = self.payload(err);
+			let extra_types = extras.iter().map(|x| x.ty(args));
+			match err.fields
+			{
+				Some(ref fields) =>
+				{
+					let extra_docs = extras.iter().map(|x| x.doc());
+					let extra_names = extras.iter().map(|x| x.ident());
+					quote!
+					{
+						#cfg
+						#variant
+						{
+							/// The `Error` which caused this one.
+							source: #spec,
+							#(#fields,)*
+							#(#[doc = #extra_docs] #extra_names: #extra_types,)*
+						}
+					}
+				},
+				None => quote!(#cfg #attrs #variant (#source_attr #spec #(, #extra_types)*)),
+			}
+		});
+
+		let (_, _, where_clause) = self.generics.split_for_impl();
+		let params = &self.generics.params;
+		let params = (!params.is_empty()).then(|| quote!(<#params>));
+		quote!
+		{
+			#(#[doc = #doc])*
+			#derives
+			#must_use
+			#non_exhaustive
+			#(#enum_attrs)*
+			#vis enum #ident #params #where_clause
+			{
+				#(#[doc = #variant_docs] #variants),*
+			}
+		}
+	}
+
+	/// The assertions that each `Error` implements [`Error`](std::error::Error), which are spanned
+	/// at the attribute argument that lists it, so that a type which is not an `Error` is reported
+	/// there rather than within the generated implementations.
+	///
+	/// Generic `Error`s are bounded by the implementations instead (and those which borrow from the
+	/// function cannot be named outside of it), and those which are displayed
+	/// with `Debug` or given `no_bound` need not be `Error`s at all. Those given `opaque` are
+	/// asserted to refer to one instead.
+	fn assertions(&self) -> Option<TokenStream2>
+	{
+		let assertions: Vec<_> = self.args.entries.iter()
+			.filter(|e| !e.generic && !e.borrowed && !e.no_bound && e.display != Some(Display::Debug) && !e.spec.is_dyn())
+			.filter(|e| !matches!(e.spec, Spec::Message(_) | Spec::Unit(_)))
+			.map(|err|
+			{
+				let cfg = err.cfg_attr();
+				let spec = &err.spec;
+				match err.opaque
+				{
+					true => quote_spanned!(spec.span()=> #cfg assert_opaque::<#spec>();),
+					false => quote_spanned!(spec.span()=> #cfg assert_error::<#spec>();),
+				}
+			})
+			.collect();
+
+		let opaque = opaque();
+		(!assertions.is_empty()).then(|| quote!
+		{
+			#[allow(dead_code)] // when every `Error` is `opaque`, or none are
+			const _: fn() = ||
+			{
+				fn assert_error<E: std::error::Error>() {}
+				fn assert_opaque<E: core::convert::AsRef<#opaque>>() {}
+				#(#assertions)*
+			};
+		})
+	}
+
+	/// The assertions that each `Error` (and the fields of its variant) is `Send` and/or `Sync`, if
+	/// the `send` or `sync` options were given, which are spanned like those of
+	/// [`assertions`](Self::assertions).
+	///
+	/// Generic (or borrowed) `Error`s cannot be named outside of the function, so they are left
+	/// unchecked.
+	fn thread_safety(&self) -> Option<TokenStream2>
+	{
+		let bounds: Vec<_> = [(self.args.send, quote!(core::marker::Send)), (self.args.sync, quote!(core::marker::Sync))].iter()
+			.filter(|(enabled, _)| *enabled)
+			.map(|(_, bound)| bound.clone())
+			.collect();
+
+		if bounds.is_empty()
+		{
+			return None;
+		}
+
+		let assertions = self.args.entries.iter().filter(|e| !e.generic && !e.borrowed).map(|err|
+		{
+			let cfg = err.cfg_attr();
+			let spec = match err.spec
+			{
+				Spec::Unit(_) => None,
+				ref spec => Some(quote_spanned!(spec.span()=> assert_thread_safe::<#spec>();)),
+			};
+			let fields = err.fields.iter().flatten().map(|f|
+			{
+				let ty = &f.ty;
+				quote_spanned!(ty.span()=> assert_thread_safe::<#ty>();)
+			});
+
+			quote!(#cfg { #spec #(#fields)* })
+		});
+
+		Some(quote!
+		{
+			const _: fn() = ||
+			{
+				fn assert_thread_safe<T: #(#bounds)+*>() {}
+				#(#assertions)*
+			};
+		})
+	}
+
+	/// The assertion that this `Error` is no larger than the `max_size` option allows, if it was
+	/// given, which is spanned by its value so that a failure is reported there.
+	fn max_size(&self) -> Option<TokenStream2>
+	{
+		let lit = self.args.max_size.as_ref()?;
+		let ident = &self.ident;
+
+		// The assertion is outside of the function, so the lifetimes of the `Error` are replaced.
+		let lifetimes: Vec<_> = self.generics.lifetimes().map(|_| quote!('static)).collect();
+		let ty = match lifetimes.is_empty()
+		{
+			true => quote!(#ident),
+			false => quote!(#ident<#(#lifetimes),*>),
+		};
+
+		let message = format!(
+			"`{}` is larger than its `max_size` of {} bytes; give its largest entries `boxed`", self.ident, lit.base10_digits(),
+		);
+
+		let mut max_size = proc_macro2::Literal::usize_unsuffixed(lit.base10_parse().ok()?);
+		max_size.set_span(lit.span());
+		Some(quote_spanned!
+		{lit.span()=>
+			const _: () = core::assert!(core::mem::size_of::<#ty>() <= #max_size, #message);
+		})
+	}
+
+	/// The format string which displays the `i`th variant, given the `placeholder` for what it wraps
+	/// (e.g. `{}`), with the prefix and code of the variant (if any).
+	fn display_fmt(&self, i: usize, placeholder: &str) -> String
+	{
+		let display_fmt = match self.args.display_prefix
+		{
+			Some(ref prefix) => format!("{}: {}", prefix.as_ref().map_or_else(|| self.function_name(), LitStr::value)
+				.replace('{', "{{")
+				.replace('}', "}}"),
+				placeholder,
+			),
+			None => placeholder.into(),
+		};
+
+		match self.codes
+		{
+			Some(ref codes) if self.args.display_code => format!("[{}] {}", codes[i], display_fmt),
+			_ => display_fmt,
+		}
+	}
+
+	/// The `#[error]` attribute of the `i`th variant (for `err`), and the attribute of the field
+	/// which wraps its `Error` (if any), when `thiserror` is the backend.
+	fn thiserror_attrs(&self, i: usize, err: &Entry) -> (TokenStream2, Option<TokenStream2>)
+	{
+		let error = match err.spec
+		{
+			_ if err.transparent => quote!(#[error(transparent)]),
+			Spec::Unit(_) =>
+			{
+				let text = self.unit_display(&self.variants[i]).replace('{', "{{").replace('}', "}}");
+				let display_fmt = self.display_fmt(i, &text);
+				quote!(#[error(#display_fmt)])
+			},
+			_ =>
+			{
+				let display_fmt = self.display_fmt(i, "{0}");
+				quote!(#[error(#display_fmt)])
+			},
+		};
+
+		// Messages are converted from text by this macro instead, since they are not `Error`s.
+		let source = match err.spec
+		{
+			Spec::Message(_) | Spec::Unit(_) => None,
+			_ if err.implements_from() => Some(quote!(#[from])),
+			_ => Some(quote!(#[source])),
+		};
+
+		(error, source)
+	}
+
+	/// The implementation of [`Display`](std::fmt::Display).
+	fn display(&self) -> TokenStream2
+	{
+		let location = self.args.location.then(|| quote!(write!(f, " (at {})", self.location())?;));
+		let arms = self.entries().enumerate().map(|(i, (err, variant))|
+		{
+			let display_fmt = match err.display.unwrap_or_default()
+			{
+				Display::Debug => self.display_fmt(i, "{:?}"),
+				Display::Display => self.display_fmt(i, "{}"),
+			};
+
+			// Spanned so that a missing `Display` implementation is reported at the attribute argument,
+			// but the variables are not, since they may be from another macro expansion (e.g. a set).
+			let (e, f) = (format_ident!("e"), format_ident!("f"));
+			if let Spec::Unit(_) = err.spec
+			{
+				let cfg = err.cfg_attr();
+				let text = self.unit_display(variant);
+				return quote!(#cfg Self::#variant => write!(#f, #display_fmt, #text));
+			}
+
+			let pattern = pattern(err, variant, quote!(ref e));
+			match err.transparent
+			{
+				// The `Error` is responsible for its own source chain, too.
+				true => quote_spanned!(err.spec.span()=> #pattern => return core::fmt::Display::fmt(#e, #f)),
+				false => quote_spanned!(err.spec.span()=> #pattern => write!(#f, #display_fmt, #e)),
+			}
+		});
+
+		// The sources are found through the implementation of `Error`.
+		let header = self.impl_header(Some(quote!(core::fmt::Display)), &self.error_bounds());
+		quote!
+		{
+			#[automatically_derived]
+			#header
+			{
+				#[allow(unreachable_code)] // when there are no variants
+				fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result
+				{
+					let result: core::fmt::Result = match *self
+					{
+						#(#arms),*
+					};
+
+					result?;
+					if f.alternate()
+					{
+						// The first source is the `Error` which was just written.
+						let mut source = std::error::Error::source(self).and_then(std::error::Error::source);
+						while let std::option::Option::Some(e) = source
+						{
+							write!(f, ": {}", e)?;
+							source = e.source();
+						}
+
+						#location
+					}
+
+					core::result::Result::Ok(())
+				}
+			}
+		}
+	}
+
+	/// The implementations of [`From`] for each `Error`.
+	fn conversions(&self) -> Vec<TokenStream2>
+	{
+		let header = |from| self.impl_header(Some(quote!(std::convert::From<#from>)), &[]);
+
+		let track_caller = self.track_caller();
+		let cold = self.cold();
+
+		// The conversions from text would overlap if there were more than one message.
+		let messages = self.args.entries.iter().filter(|e| matches!(e.spec, Spec::Message(_))).count();
+
+		// Spanning each `From` impl at its attribute argument makes overlapping impls (e.g. `io::Error`
+		// and `std::io::Error`) point back at the attribute instead of the expansion. The variable is
+		// not spanned, since the argument may be from another macro expansion (e.g. a set).
+		let e = format_ident!("e");
+		self.entries().filter_map(|(err, variant)|
+		{
+			// Other `Error`s are converted by `#[from]` when `thiserror` is the backend.
+			if !err.implements_from() || (self.args.backend == Some(Backend::Thiserror) && !matches!(err.spec, Spec::Message(_)))
+			{
+				return None;
+			}
+
+			let cfg = err.cfg_attr();
+			let spec = &err.spec;
+			let from_str_header = header(quote!(&str));
+			let from_str = match *spec
+			{
+				Spec::Message(_) if messages > 1 => return None,
+				Spec::Message(_) =>
+				{
+					let construct = self.construct(err, variant, quote!(e.into()));
+					let breadcrumb = self.breadcrumb(err, variant);
+					Some(quote_spanned!
+					{spec.span()=>
+						#cfg
+						#[automatically_derived]
+						#from_str_header
+						{
+							#track_caller
+							#cold
+							fn from(#e: &str) -> Self
+							{
+								#breadcrumb
+								#construct
+							}
+						}
+					})
+				},
+				_ => None,
+			};
+
+			let construct = self.construct(err, variant, quote!(e));
+			let breadcrumb = self.breadcrumb(err, variant);
+			let header = header(quote!(#spec));
+
+			Some(quote_spanned!
+			{spec.span()=>
+				#cfg
+				#[automatically_derived]
+				#header
+				{
+					#track_caller
+					#cold
+					fn from(#e: #spec) -> Self
+					{
+						#breadcrumb
+						#construct
+					}
+				}
+
+				#from_str
+			})
+		}).collect()
+	}
+
+	/// The conversion from this `Error` into a [`std::io::Error`], which wraps it as the source of
+	/// an `Other` error so that it can be downcast back.
+	fn io_conversion(&self) -> TokenStream2
+	{
+		let (impl_generics, _, where_clause) = self.generics.split_for_impl();
+		let ty = self.ty();
+		let predicates = where_clause.iter().flat_map(|w| &w.predicates);
+		let cold = self.cold();
+		quote!
+		{
+			#[automatically_derived]
+			impl #impl_generics std::convert::From<#ty> for std::io::Error
+				where #(#predicates,)* #ty: std::error::Error + core::marker::Send + core::marker::Sync + 'static
+			{
+				#cold
+				fn from(e: #ty) -> Self
+				{
+					Self::other(e)
+				}
+			}
+		}
+	}
+
+	/// The implementation of `AsRef<dyn Error>` for this `Error`, and the assertion that it converts
+	/// into a `Box<dyn Error + Send + Sync>` (which the standard library implements for it already).
+	///
+	/// An `Error` which refers to the generic parameters of the function cannot be named outside of
+	/// it, so it is left unchecked, like those asserted by [`thread_safety`](Self::thread_safety).
+	fn dyn_impls(&self) -> TokenStream2
+	{
+		let assertion = self.generics.params.is_empty().then(||
+		{
+			let ident = &self.ident;
+			quote!
+			{
+				const _: fn() = ||
+				{
+					fn assert_boxable<E: std::convert::Into<std::boxed::Box<dyn std::error::Error + core::marker::Send + core::marker::Sync>>>() {}
+					assert_boxable::<#ident>();
+				};
+			}
+		});
+
+		let mut bounds = self.error_bounds();
+		bounds.push(quote!(Self: 'static));
+
+		let header = self.impl_header(Some(quote!(core::convert::AsRef<dyn std::error::Error + 'static>)), &bounds);
+		quote!
+		{
+			#assertion
+
+			#[automatically_derived]
+			#header
+			{
+				fn as_ref(&self) -> &(dyn std::error::Error + 'static)
+				{
+					self
+				}
+			}
+		}
+	}
+
+	/// The implementations of [`From`] for each `Error` given by the `into` option, which convert
+	/// the `Error` wrapped by each variant into it.
+	fn outer_conversions(&self) -> Vec<TokenStream2>
+	{
+		let ident = &self.ident;
+		let e = format_ident!("e");
+		let (impl_generics, _, where_clause) = self.generics.split_for_impl();
+		let ty = self.ty();
+		let cold = self.cold();
+
+		self.args.into.iter().map(|outer|
+		{
+			let arms = self.entries().map(|(err, variant)|
+			{
+				// `Self` is the outer `Error` here.
+				let cfg = err.cfg_attr();
+				let pattern = match err.fields
+				{
+					Some(_) => quote!(#cfg #ident::#variant {source: e, ..}),
+					None => quote!(#cfg #ident::#variant(e, ..)),
+				};
+
+				let e = match err.boxed
+				{
+					true => quote!(*e),
+					false => quote!(e),
+				};
+
+				quote!(#pattern => std::convert::From::from(#e))
+			});
+
+			// The bounds on generic `Error`s cannot be checked until the generated `Error` is used.
+			let predicates: Vec<_> = where_clause.iter().flat_map(|w| &w.predicates).map(ToTokens::to_token_stream)
+				.chain(self.args.entries.iter().filter(|e| e.generic).map(|e|
+				{
+					let spec = &e.spec;
+					quote!(#outer: std::convert::From<#spec>)
+				}))
+				.collect();
+
+			let where_clause = (!predicates.is_empty()).then(|| quote!(where #(#predicates),*));
+			quote_spanned!
+			{outer.span()=>
+				#[automatically_derived]
+				impl #impl_generics std::convert::From<#ty> for #outer #where_clause
+				{
+					#cold
+					fn from(#e: #ty) -> Self
+					{
+						match #e
+						{
+							#(#arms),*
+						}
+					}
+				}
+			}
+		}).collect()
+	}
+
+	/// The implementations of [`From`] for each `Error` given to the `flatten` option, which convert
+	/// each of its variants into the variant of the same name.
+	fn flattened_conversions(&self) -> Vec<TokenStream2>
+	{
+		let e = format_ident!("e");
+		let mut inners: Vec<&syn::Path> = Vec::new();
+		for inner in self.args.entries.iter().filter_map(|e| e.flattened.as_ref())
+		{
+			if !inners.iter().any(|i| written(i) == written(inner))
+			{
+				inners.push(inner);
+			}
+		}
+
+		inners.into_iter().map(|inner|
+		{
+			let arms = self.entries().filter(|(err, _)| err.flattened.as_ref().is_some_and(|i| written(i) == written(inner))).map(|(err, variant)|
+			{
+				let construct = self.construct(err, variant, quote!(e));
+				let breadcrumb = self.breadcrumb(err, variant);
+				quote!(#inner::#variant(e, ..) => { #breadcrumb #construct })
+			});
+
+			let header = self.impl_header(Some(quote!(std::convert::From<#inner>)), &[]);
+			let track_caller = self.track_caller();
+			let cold = self.cold();
+			quote_spanned!
+			{inner.span()=>
+				#[automatically_derived]
+				#header
+				{
+					#track_caller
+					#cold
+					fn from(#e: #inner) -> Self
+					{
+						match #e
+						{
+							#(#arms),*
+						}
+					}
+				}
+			}
+		}).collect()
+	}
+
+	/// The statements which log the conversion of an `Error` `e` into the `variant` of `err`, given
+	/// to the `log` and `tracing` options.
+	fn breadcrumb(&self, err: &Entry, variant: &Ident) -> TokenStream2
+	{
+		let placeholder = match err.display.unwrap_or_default()
+		{
+			Display::Debug => "{:?}",
+			Display::Display => "{}",
+		};
+
+		let function = self.function_name();
+		let severity = err.severity.map(|s| format_ident!("{}", s.level()));
+		let level = |level: &Option<Level>| match err.level
+		{
+			Some(ref level) => Some(level.ident.clone()),
+			None => level.as_ref().map(|l| severity.clone().unwrap_or_else(|| l.ident.clone())),
+		};
+		let log = level(&self.args.log).map(|level|
+		{
+			let format = format!("{}: {}", function.replace('{', "{{").replace('}', "}}"), placeholder);
+			quote!(log::#level!(target: "erro_rs", #format, e);)
+		});
+
+		let tracing = level(&self.args.tracing).map(|level|
+		{
+			let variant = variant.unraw().to_string();
+			quote!(tracing::#level!(target: "erro_rs", function = #function, variant = #variant, #placeholder, e);)
+		});
+
+		quote!(#log #tracing)
+	}
+
+	/// `#[cold]` and `#[inline(never)]`, if the functions which construct this `Error` should be
+	/// kept out of the paths which do not.
+	fn cold(&self) -> Option<TokenStream2>
+	{
+		self.args.cold.then(|| quote!(#[cold] #[inline(never)]))
+	}
+
+	/// `#[track_caller]`, if the location of the caller is captured when constructing this `Error`.
+	fn track_caller(&self) -> Option<TokenStream2>
+	{
+		self.args.location.then(|| quote!(#[track_caller]))
+	}
+
+	/// An expression which constructs the `variant` of `err` from its `source`, and its additional
+	/// fields (if any) from variables of the same name.
+	fn construct(&self, err: &Entry, variant: &Ident, source: TokenStream2) -> TokenStream2
+	{
+		let source = match (self.arc(err), err.boxed)
+		{
+			(true, _) => quote!(std::sync::Arc::new(#source)),
+			(false, true) => quote!(std::boxed::Box::new(#source)),
+			(false, false) => source,
+		};
+
+		let extras = self.extras();
+		let extra_values = extras.iter().map(|x| x.value());
+		match err.fields
+		{
+			Some(ref fields) =>
+			{
+				let names = fields.iter().map(|f| &f.ident);
+				let extra_names = extras.iter().map(|x| x.ident());
+				quote!(Self::#variant {source: #source #(, #names)* #(, #extra_names: #extra_values)*})
+			},
+			None => quote!(Self::#variant(#source #(, #extra_values)*)),
+		}
+	}
+
+	/// The constructors of each variant which has additional fields (or every variant, if they
+	/// capture a backtrace or location).
+	fn constructors(&self) -> Option<TokenStream2>
+	{
+		let Self {ident, vis, ..} = self;
+		let constructors: Vec<_> = self.entries().filter(|(err, _)| self.args.backtrace || self.args.location || err.fields.is_some()).map(|(err, variant)|
+		{
+			let spec = &err.spec;
+			let fields = err.fields.iter().flatten();
+
+			let constructor = method_ident(&method_name(variant));
+			let doc = format!("Construct a [`{}::{}`].", ident, variant);
+			let names = fields.clone().map(|f| &f.ident);
+			let types = fields.map(|f| &f.ty);
+			let construct = self.construct(err, variant, quote!(source));
+
+			let cfg = err.cfg_attr();
+			let track_caller = self.track_caller();
+			let cold = self.cold();
+			quote!
+			{
+				#cfg
+				#[doc = #doc]
+				#track_caller
+				#cold
+				#vis fn #constructor(source: #spec #(, #names: #types)*) -> Self
+				{
+					#construct
+				}
+			}
+		}).collect();
+
+		let header = self.impl_header(None, &[]);
+		(!constructors.is_empty()).then(|| quote!
+		{
+			#header
+			{
+				#(#constructors)*
+			}
+		})
+	}
+
+	/// The extension trait which adds context to `Result`s, for each variant which has additional
+	/// fields.
+	fn context(&self) -> Option<TokenStream2>
+	{
+		let Self {generics, ident, vis, ..} = self;
+		let trait_ident = format_ident!("{}Context", ident);
+		let ty = self.ty();
+
+		// Named so as not to shadow the parameters of the function.
+		let t = generics::fresh("T", generics);
+		let e = generics::fresh("E", generics);
+
+		let methods: Vec<_> = self.entries().filter_map(|(err, variant)|
+		{
+			let fields = err.fields.as_ref()?;
+			let spec = &err.spec;
+
+			let method = format_ident!("ctx_{}", method_name(variant));
+			let constructor = method_ident(&method_name(variant));
+			let doc = format!("Convert the `Error` of this `Result` into a [`{}::{}`] with the given fields.", ident, variant);
+			let names: Vec<_> = fields.iter().map(|f| &f.ident).collect();
+			let types = fields.iter().map(|f| &f.ty);
+
+			let cfg = err.cfg_attr();
+			let track_caller = self.track_caller();
+			Some(quote!
+			{
+				#cfg
+				#[doc = #doc]
+				#track_caller
+				fn #method(self #(, #names: impl std::convert::Into<#types>)*) -> std::result::Result<#t, #ty>
+					where Self: std::marker::Sized + std::convert::Into<std::result::Result<#t, #spec>>
+				{
+					// Not `map_err`, so that the location of the caller is tracked through the closure.
+					match self.into()
+					{
+						std::result::Result::Ok(t) => std::result::Result::Ok(t),
+						std::result::Result::Err(source) => std::result::Result::Err(#ident::#constructor(source #(, #names.into())*)),
+					}
+				}
+			})
+		}).collect();
+
+		if methods.is_empty()
+		{
+			return None;
+		}
+
+		let doc = format!("Adds context to `Result`s, converting their `Error`s into a [`{}`].", ident);
+		// Lifetimes must come before the other parameters.
+		let lifetimes: Vec<_> = generics.lifetimes().collect();
+		let lifetime_args: Vec<_> = lifetimes.iter().map(|l| &l.lifetime).collect();
+		let params: Vec<_> = generics.type_params().collect();
+		let impl_params = &params;
+		let args = generics.type_params().map(|p| &p.ident);
+		let where_clause = &generics.where_clause;
+		Some(quote!
+		{
+			#[doc = #doc]
+			#vis trait #trait_ident<#(#lifetimes,)* #t #(, #params)*> #where_clause
+			{
+				#(#methods)*
+			}
+
+			impl<#(#lifetimes,)* #t, #e #(, #impl_params)*> #trait_ident<#(#lifetime_args,)* #t #(, #args)*> for std::result::Result<#t, #e> #where_clause {}
+		})
+	}
+
+	/// The extension trait which converts `None` into the variant added by the `option` option, if
+	/// it was given.
+	fn option(&self) -> Option<TokenStream2>
+	{
+		let Self {generics, ident, vis, ..} = self;
+		let (err, variant) = self.entries().find(|(e, _)| matches!(e.spec, Spec::Unit(_)))?;
+		let trait_ident = format_ident!("{}Option", ident);
+		let ty = self.ty();
+
+		// Named so as not to shadow the parameters of the function.
+		let t = generics::fresh("T", generics);
+		let e = generics::fresh("E", generics);
+
+		let method = format_ident!("or_{}", method_name(variant));
+		let doc = format!("Unwrap this `Option`, or return a [`{}::{}`] (converted into `E`) if it is `None`.", ident, variant);
+		let trait_doc = format!("Converts `None` into a [`{}::{}`].", ident, variant);
+		let cfg = err.cfg_attr();
+		let lifetimes: Vec<_> = generics.lifetimes().collect();
+		let lifetime_args: Vec<_> = lifetimes.iter().map(|l| &l.lifetime).collect();
+		let params: Vec<_> = generics.type_params().collect();
+		let impl_params = &params;
+		let args = generics.type_params().map(|p| &p.ident);
+		let where_clause = &generics.where_clause;
+		Some(quote!
+		{
+			#cfg
+			#[doc = #trait_doc]
+			#vis trait #trait_ident<#(#lifetimes,)* #t #(, #params)*> #where_clause
+			{
+				#[doc = #doc]
+				fn #method<#e>(self) -> std::result::Result<#t, #e>
+					where Self: std::marker::Sized + std::convert::Into<std::option::Option<#t>>, #e: std::convert::From<#ty>
+				{
+					match self.into()
+					{
+						std::option::Option::Some(t) => std::result::Result::Ok(t),
+						std::option::Option::None => std::result::Result::Err(std::convert::From::from(#ident::#variant)),
+					}
+				}
+			}
+
+			#cfg
+			impl<#(#lifetimes,)* #t #(, #impl_params)*> #trait_ident<#(#lifetime_args,)* #t #(, #args)*> for std::option::Option<#t> #where_clause {}
+		})
+	}
+
+	/// The implementation of [`Error`](std::error::Error).
+	fn error_impl(&self) -> TokenStream2
+	{
+		let provide = cfg!(feature = "nightly-provide").then(|| self.provide());
+		let sources = self.entries().map(|(err, variant)|
+		{
+			let pattern = pattern(err, variant, quote!(ref e));
+			let e = self.unboxed(err);
+			// A `source` must be `'static`.
+			if err.borrowed || err.display == Some(Display::Debug) || err.no_bound || matches!(err.spec, Spec::Message(_) | Spec::Unit(_))
+			{
+				let wildcard = wildcard(err, variant);
+				quote!(#wildcard => std::option::Option::None)
+			}
+			else if err.opaque
+			{
+				let opaque = opaque();
+				let e = quote!(core::convert::AsRef::<#opaque>::as_ref(#e));
+				match err.transparent
+				{
+					true => quote!(#pattern => std::error::Error::source(#e)),
+					false => quote!(#pattern => std::option::Option::Some(#e)),
+				}
+			}
+			else if err.transparent && err.spec.is_dyn()
+			{
+				quote!(#pattern => std::error::Error::source(&**#e))
+			}
+			else if err.transparent
+			{
+				quote!(#pattern => std::error::Error::source(#e))
+			}
+			else if err.spec.is_dyn()
+			{
+				quote!(#pattern => std::option::Option::Some(std::convert::AsRef::as_ref(#e)))
+			}
+			else
+			{
+				quote!(#pattern => std::option::Option::Some(#e))
+			}
+		});
+
+		let header = self.impl_header(Some(quote!(std::error::Error)), &self.error_bounds());
+		quote!
+		{
+			#[automatically_derived]
+			#header
+			{
+				fn source(&self) -> std::option::Option<&(dyn std::error::Error + 'static)>
+				{
+					match *self
+					{
+						#(#sources),*
+					}
+				}
+
+				#provide
+			}
+		}
+	}
+
+	/// The implementation of [`Debug`](core::fmt::Debug), which reports the `Display` of this
+	/// `Error` followed by that of each of its sources, like `anyhow` does.
+	fn report_debug(&self) -> TokenStream2
+	{
+		let header = self.impl_header(Some(quote!(core::fmt::Debug)), &self.error_bounds());
+		quote!
+		{
+			#header
+			{
+				fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result
+				{
+					core::fmt::Display::fmt(self, f)?;
+
+					let sources = core::iter::successors(std::error::Error::source(self), |e| e.source());
+					let numbered = sources.clone().nth(1).is_some();
+					for (i, source) in sources.enumerate()
+					{
+						if i == 0
+						{
+							f.write_str("\n\nCaused by:")?;
+						}
+
+						match numbered
+						{
+							true => write!(f, "\n    {}: {}", i, source)?,
+							false => write!(f, "\n    {}", source)?,
+						}
+					}
+
+					core::result::Result::Ok(())
+				}
+			}
+		}
+	}
+
+	/// The implementation of [`Error::provide`](std::error::Error), which forwards the backtrace
+	/// (if any) and then delegates to the `Error` being wrapped.
+	fn provide(&self) -> TokenStream2
+	{
+		let backtrace = self.args.backtrace.then(|| quote!
+		{
+			request.provide_ref::<std::backtrace::Backtrace>(self.backtrace());
+		});
+
+		let arms = self.entries().map(|(err, variant)|
+		{
+			let pattern = pattern(err, variant, quote!(ref e));
+			let wildcard = wildcard(err, variant);
+			let e = self.unboxed(err);
+			match err.display == Some(Display::Debug) || err.no_bound || matches!(err.spec, Spec::Message(_) | Spec::Unit(_))
+			{
+				true => quote!(#wildcard => ()),
+				false if err.opaque =>
+				{
+					let opaque = opaque();
+					quote!(#pattern => std::error::Error::provide(core::convert::AsRef::<#opaque>::as_ref(#e), request))
+				},
+				false if err.spec.is_dyn() => quote!(#pattern => std::error::Error::provide(&**#e, request)),
+				false => quote!(#pattern => std::error::Error::provide(#e, request)),
+			}
+		});
+
+		quote!
+		{
+			fn provide<'request>(&'request self, request: &mut std::error::Request<'request>)
+			{
+				#backtrace
+				match *self
+				{
+					#(#arms),*
+				}
+			}
+		}
+	}
+
+	/// The method generated by the `backtrace` option.
+	fn backtrace(&self) -> TokenStream2
+	{
+		let vis = &self.vis;
+		let arms = self.entries().map(|(err, variant)| self.extra_pattern(err, variant, Extra::Backtrace, quote!(ref backtrace)));
+
+		let header = self.impl_header(None, &[]);
+		quote!
+		{
+			#header
+			{
+				/// The backtrace captured when this `Error` was created.
+				#vis fn backtrace(&self) -> &std::backtrace::Backtrace
+				{
+					match *self
+					{
+						#(#arms => backtrace),*
+					}
+				}
+			}
+		}
+	}
+
+	/// The method generated by the `location` option.
+	fn location(&self) -> TokenStream2
+	{
+		let vis = &self.vis;
+		let arms = self.entries().map(|(err, variant)| self.extra_pattern(err, variant, Extra::Location, quote!(location)));
+
+		let header = self.impl_header(None, &[]);
+		quote!
+		{
+			#header
+			{
+				/// Where this `Error` was created, e.g. the `?` which converted it.
+				#vis fn location(&self) -> &'static std::panic::Location<'static>
+				{
+					match *self
+					{
+						#(#arms => location),*
+					}
+				}
+			}
+		}
+	}
+
+	/// The methods generated by the `capture` option.
+	fn captures(&self) -> TokenStream2
+	{
+		let Self {args, vis, ..} = self;
+		let ty = Extra::Captures.ty(args);
+		let by_ref: Vec<_> = self.entries().map(|(err, variant)| self.extra_pattern(err, variant, Extra::Captures, quote!(ref captures))).collect();
+		let by_mut = self.entries().map(|(err, variant)| self.extra_pattern(err, variant, Extra::Captures, quote!(ref mut captures)));
+
+		let accessors = args.capture.iter().enumerate().map(|(i, arg)|
+		{
+			let accessor = format_ident!("captured_{}", arg.unraw());
+			let doc = format!("The `Debug` representation of the `{}` argument which [`{}`] was called with.", arg, self.function);
+			quote!
+			{
+				#[doc = #doc]
+				#vis fn #accessor(&self) -> &str
+				{
+					&self.captures()[#i]
+				}
+			}
+		});
+
+		let header = self.impl_header(None, &[]);
+		quote!
+		{
+			#header
+			{
+				#(#accessors)*
+
+				/// The `Debug` representation of each argument captured from the function.
+				fn captures(&self) -> &#ty
+				{
+					match *self
+					{
+						#(#by_ref => captures),*
+					}
+				}
+
+				/// The `Debug` representation of each argument captured from the function.
+				fn captures_mut(&mut self) -> &mut #ty
+				{
+					match *self
+					{
+						#(#by_mut => captures),*
+					}
+				}
+			}
+		}
+	}
+
+	/// Whether the variant for `err` stores the `Error` in an [`Arc`](std::sync::Arc), because of
+	/// the `clone` option.
+	fn arc(&self, err: &Entry) -> bool
+	{
+		self.args.clone == Some(CloneBy::Arc) && !err.no_arc && !matches!(err.spec, Spec::Message(_) | Spec::Unit(_))
+	}
+
+	/// The type which the variant for `err` stores the `Error` as.
+	fn payload(&self, err: &Entry) -> TokenStream2
+	{
+		let spec = &err.spec;
+		match (self.arc(err), err.boxed)
+		{
+			(true, _) => quote!(std::sync::Arc<#spec>),
+			(false, true) => quote!(std::boxed::Box<#spec>),
+			(false, false) => quote!(#spec),
+		}
+	}
+
+	/// A reference to the `Error` wrapped by `err`, given a reference `e` to its payload.
+	fn unboxed(&self, err: &Entry) -> TokenStream2
+	{
+		match self.arc(err) || err.boxed
+		{
+			true => quote!(&**e),
+			false => quote!(e),
+		}
+	}
+
+	/// The fields which every variant stores after the `Error` it wraps (and its additional fields),
+	/// in order.
+	fn extras(&self) -> Vec<Extra>
+	{
+		let Args {backtrace, location, ..} = *self.args;
+		[(backtrace, Extra::Backtrace), (location, Extra::Location), (!self.args.capture.is_empty(), Extra::Captures)].iter()
+			.filter(|&&(enabled, _)| enabled)
+			.map(|&(_, extra)| extra)
+			.collect()
+	}
+
+	/// A match arm pattern (behind the `cfg` of `err`, if any) which matches the `variant` of `err`,
+	/// binding its `extra` field to `binding`.
+	fn extra_pattern(&self, err: &Entry, variant: &Ident, extra: Extra, binding: TokenStream2) -> TokenStream2
+	{
+		let cfg = err.cfg_attr();
+		match err.fields
+		{
+			Some(_) =>
+			{
+				let name = extra.ident();
+				quote!(#cfg Self::#variant {#name: #binding, ..})
+			},
+			None =>
+			{
+				// The `Error` comes before the extra fields.
+				let skipped = (0..=self.extras().iter().position(|x| *x == extra).unwrap_or_default()).map(|_| quote!(_));
+				quote!(#cfg Self::#variant(#(#skipped,)* #binding, ..))
+			},
+		}
+	}
+
+	/// The methods generated by the `helpers` option.
+	fn helpers(&self) -> TokenStream2
+	{
+		let Self {ident, vis, ..} = self;
+
+		let methods = self.entries().map(|(err, variant)|
+		{
+			let name = method_name(variant);
+			let spec = &err.spec;
+
+			let cfg = err.cfg_attr();
+			let predicate = format_ident!("is_{}", name);
+			let predicate_doc = format!("Whether this is a [`{}::{}`].", ident, variant);
+			let predicate = quote!
+			{
+				#cfg
+				#[doc = #predicate_doc]
+				#vis fn #predicate(&self) -> bool
+				{
+					core::matches!(*self, Self::#variant {..})
+				}
+			};
+
+			// There is no `Error` to access.
+			if let Spec::Unit(_) = err.spec
+			{
+				return predicate;
+			}
+
+			let accessor = format_ident!("as_{}", name);
+			let accessor_doc = format!("The `Error` wrapped by this [`{}::{}`], if it is one.", ident, variant);
+
+			let by_ref = pattern(err, variant, quote!(ref e));
+			let by_value = pattern(err, variant, quote!(e));
+			let e = self.unboxed(err);
+
+			// An `Error` shared by an `Arc` cannot be moved out of it.
+			let (owned, owned_ty) = match (self.arc(err), err.boxed)
+			{
+				(true, _) => (quote!(e), quote!(std::sync::Arc<#spec>)),
+				(false, true) => (quote!(*e), quote!(#spec)),
+				(false, false) => (quote!(e), quote!(#spec)),
+			};
+
+			let conversion = format_ident!("into_{}", name);
+			let conversion_doc = format!(
+				"Convert this [`{}::{}`] into the `Error` it wraps, or return `self` if it is another variant.",
+				ident, variant,
+			);
+
+			quote!
+			{
+				#predicate
+
+				#cfg
+				#[doc = #accessor_doc]
+				#vis fn #accessor(&self) -> std::option::Option<&#spec>
+				{
+					match *self
+					{
+						#by_ref => std::option::Option::Some(#e),
+						#[allow(unreachable_patterns)]
+						_ => std::option::Option::None,
+					}
+				}
+
+				#cfg
+				#[doc = #conversion_doc]
+				#vis fn #conversion(self) -> std::result::Result<#owned_ty, Self>
+				{
+					match self
+					{
+						#by_value => std::result::Result::Ok(#owned),
+						#[allow(unreachable_patterns)]
+						other => std::result::Result::Err(other),
+					}
+				}
+			}
+		});
+
+		let header = self.impl_header(None, &[]);
+		quote!
+		{
+			#header
+			{
+				#(#methods)*
+			}
+		}
+	}
+
+	/// The `code` method of this `Error` (and its kind, if any), if its variants have codes.
+	fn code(&self) -> Option<TokenStream2>
+	{
+		let Self {variants, vis, ..} = self;
+		let codes = self.codes.as_ref()?;
+		let cfgs: Vec<_> = self.args.entries.iter().map(Entry::cfg_attr).collect();
+
+		let kind_code = (self.args.kind || self.args.eq_kind).then(||
+		{
+			let kind = self.kind_ident();
+			quote!
+			{
+				impl #kind
+				{
+					/// The numeric code of this kind of `Error`.
+					#vis const fn code(self) -> u32
+					{
+						match self
+						{
+							#(#cfgs Self::#variants => #codes),*
+						}
+					}
+				}
+			}
+		});
+
+		let header = self.impl_header(None, &[]);
+		Some(quote!
+		{
+			#header
+			{
+				/// The numeric code of this `Error`.
+				#vis fn code(&self) -> u32
+				{
+					match *self
+					{
+						#(#cfgs Self::#variants {..} => #codes),*
+					}
+				}
+			}
+
+			#kind_code
+		})
+	}
+
+	/// The `exit_code` method of this `Error`, and the conversion into an
+	/// [`ExitCode`](std::process::ExitCode), if any variant has an exit code.
+	fn exit_code(&self) -> Option<TokenStream2>
+	{
+		let Self {variants, vis, ..} = self;
+		if self.args.entries.iter().all(|e| e.exit_code.is_none())
+		{
+			return None;
+		}
+
+		let cfgs = self.args.entries.iter().map(Entry::cfg_attr);
+		let exit_codes = self.args.entries.iter().map(|e| e.exit_code.unwrap_or(1));
+		let (impl_generics, _, where_clause) = self.generics.split_for_impl();
+		let ty = self.ty();
+		let header = self.impl_header(None, &[]);
+		Some(quote!
+		{
+			#header
+			{
+				/// The exit code of the process when it fails with this `Error`.
+				#vis fn exit_code(&self) -> u8
+				{
+					match *self
+					{
+						#(#cfgs Self::#variants {..} => #exit_codes),*
+					}
+				}
+			}
+
+			#[automatically_derived]
+			impl #impl_generics std::convert::From<#ty> for std::process::ExitCode #where_clause
+			{
+				fn from(e: #ty) -> Self
+				{
+					Self::from(e.exit_code())
+				}
+			}
+		})
+	}
+
+	/// The `is_retryable` method of this `Error` (and its kind, if any), if any variant is
+	/// retryable.
+	fn retryable(&self) -> Option<TokenStream2>
+	{
+		let Self {variants, vis, ..} = self;
+		if self.args.entries.iter().all(|e| !e.retryable)
+		{
+			return None;
+		}
+
+		let cfgs: Vec<_> = self.args.entries.iter().map(Entry::cfg_attr).collect();
+		let retryable: Vec<_> = self.args.entries.iter().map(|e| e.retryable).collect();
+
+		let kind_retryable = (self.args.kind || self.args.eq_kind).then(||
+		{
+			let kind = self.kind_ident();
+			quote!
+			{
+				impl #kind
+				{
+					/// Whether the operation which failed with this kind of `Error` may succeed if it
+					/// is tried again.
+					#vis const fn is_retryable(self) -> bool
+					{
+						match self
+						{
+							#(#cfgs Self::#variants => #retryable),*
+						}
+					}
+				}
+			}
+		});
+
+		let header = self.impl_header(None, &[]);
+		Some(quote!
+		{
+			#header
+			{
+				/// Whether the operation which failed with this `Error` may succeed if it is tried
+				/// again.
+				#vis fn is_retryable(&self) -> bool
+				{
+					match *self
+					{
+						#(#cfgs Self::#variants {..} => #retryable),*
+					}
+				}
+			}
+
+			#kind_retryable
+		})
+	}
+
+	/// The `severity` method of this `Error`, if any variant has a severity.
+	fn severity(&self) -> Option<TokenStream2>
+	{
+		let Self {variants, vis, ..} = self;
+		if self.args.entries.iter().all(|e| e.severity.is_none())
+		{
+			return None;
+		}
+
+		let cfgs = self.args.entries.iter().map(Entry::cfg_attr);
+		let severities = self.args.entries.iter().map(|e| e.severity.unwrap_or_default().as_str());
+		let header = self.impl_header(None, &[]);
+		Some(quote!
+		{
+			#header
+			{
+				/// How severe this `Error` is: `"warn"`, `"error"`, or `"critical"`.
+				#vis fn severity(&self) -> &'static str
+				{
+					match *self
+					{
+						#(#cfgs Self::#variants {..} => #severities),*
+					}
+				}
+			}
+		})
+	}
+
+	/// The `status_code` method of this `Error`, if any variant has an HTTP status code (or the
+	/// `into_response` option, which responds with it, is given).
+	fn status_code(&self) -> Option<TokenStream2>
+	{
+		let Self {variants, vis, ..} = self;
+		if !self.args.into_response && self.args.entries.iter().all(|e| e.status.is_none())
+		{
+			return None;
+		}
+
+		let cfgs = self.args.entries.iter().map(Entry::cfg_attr);
+		let statuses = self.args.entries.iter().map(|e| e.status.unwrap_or(500));
+		let header = self.impl_header(None, &[]);
+		Some(quote!
+		{
+			#header
+			{
+				/// The HTTP status code of the response when a request fails with this `Error`.
+				#vis fn status_code(&self) -> u16
+				{
+					match *self
+					{
+						#(#cfgs Self::#variants {..} => #statuses),*
+					}
+				}
+			}
+		})
+	}
+
+	/// The implementation of `defmt::Format` for this `Error`, which writes the name of each variant
+	/// and the `Error` it wraps, through `defmt::Debug2Format` unless the entry is given `defmt`.
+	fn defmt(&self) -> TokenStream2
+	{
+		let arms = self.entries().map(|(err, variant)|
+		{
+			if let Spec::Unit(_) = err.spec
+			{
+				let cfg = err.cfg_attr();
+				let format = variant.unraw().to_string();
+				return quote!(#cfg Self::#variant => defmt::write!(f, #format));
+			}
+
+			let format = format!("{}({{}})", variant.unraw());
+			let pattern = pattern(err, variant, quote!(ref e));
+			let unboxed = self.unboxed(err);
+			match err.defmt
+			{
+				true => quote_spanned!(err.spec.span()=> #pattern => defmt::write!(f, #format, #unboxed)),
+				false => quote!(#pattern => defmt::write!(f, #format, defmt::Debug2Format(#unboxed))),
+			}
+		});
+
+		let mut bounds = self.error_bounds();
+		bounds.extend(self.args.entries.iter().filter(|e| e.generic && e.defmt).map(|e|
+		{
+			let spec = &e.spec;
+			quote!(#spec: defmt::Format)
+		}));
+
+		let header = self.impl_header(Some(quote!(defmt::Format)), &bounds);
+		quote!
+		{
+			#[automatically_derived]
+			#header
+			{
+				fn format(&self, f: defmt::Formatter<'_>)
+				{
+					match *self
+					{
+						#(#arms),*
+					}
+				}
+			}
+		}
+	}
+
+	/// The implementation of `miette::Diagnostic` for this `Error`, whose code is the code of each
+	/// variant (if any), and whose help and source are given by the entry options of each variant.
+	fn diagnostic(&self) -> TokenStream2
+	{
+		let code = self.codes.as_ref().map(|_| quote!
+		{
+			fn code<'a>(&'a self) -> core::option::Option<std::boxed::Box<dyn core::fmt::Display + 'a>>
+			{
+				core::option::Option::Some(std::boxed::Box::new(self.code()))
+			}
+		});
+
+		let help = self.args.entries.iter().any(|e| e.help.is_some()).then(||
+		{
+			let arms = self.entries().map(|(err, variant)|
+			{
+				let wildcard = wildcard(err, variant);
+				match err.help
+				{
+					Some(ref help) => quote!(#wildcard => core::option::Option::Some(std::boxed::Box::new(#help))),
+					None => quote!(#wildcard => core::option::Option::None),
+				}
+			});
+
+			quote!
+			{
+				fn help<'a>(&'a self) -> core::option::Option<std::boxed::Box<dyn core::fmt::Display + 'a>>
+				{
+					match *self
+					{
+						#(#arms),*
+					}
+				}
+			}
+		});
+
+		let diagnostic_source = self.args.entries.iter().any(|e| e.diagnostic_source).then(||
+		{
+			let arms = self.entries().map(|(err, variant)| match err.diagnostic_source
+			{
+				true =>
+				{
+					let pattern = pattern(err, variant, quote!(ref e));
+					let unboxed = self.unboxed(err);
+					quote_spanned!(err.spec.span()=> #pattern => core::option::Option::Some(#unboxed))
+				},
+				false =>
+				{
+					let wildcard = wildcard(err, variant);
+					quote!(#wildcard => core::option::Option::None)
+				},
+			});
+
+			quote!
+			{
+				fn diagnostic_source(&self) -> core::option::Option<&dyn miette::Diagnostic>
+				{
+					match *self
+					{
+						#(#arms),*
+					}
+				}
+			}
+		});
+
+		let mut bounds = self.error_bounds();
+		bounds.extend(self.args.entries.iter().filter(|e| e.generic && e.diagnostic_source).map(|e|
+		{
+			let spec = &e.spec;
+			quote!(#spec: miette::Diagnostic)
+		}));
+
+		let header = self.impl_header(Some(quote!(miette::Diagnostic)), &bounds);
+		quote!
+		{
+			#[automatically_derived]
+			#header
+			{
+				#code
+				#help
+				#diagnostic_source
+			}
+		}
+	}
+
+	/// The implementation of `serde::Serialize` for this `Error`, which writes the name of each
+	/// variant and the `Display` of the `Error` it wraps (and its code, if any).
+	fn serialize(&self) -> TokenStream2
+	{
+		let arms = self.entries().map(|(err, variant)|
+		{
+			let kind = variant.unraw().to_string();
+			if let Spec::Unit(_) = err.spec
+			{
+				let cfg = err.cfg_attr();
+				let text = self.unit_display(variant);
+				return quote!(#cfg Self::#variant => (#kind, std::string::String::from(#text)));
+			}
+
+			let message_fmt = match err.display.unwrap_or_default()
+			{
+				Display::Debug => "{:?}",
+				Display::Display => "{}",
+			};
+
+			let pattern = pattern(err, variant, quote!(ref e));
+			quote!(#pattern => (#kind, format!(#message_fmt, e)))
+		});
+
+		let name = self.ident.unraw().to_string();
+		let code = self.codes.as_ref().map(|_| quote!(state.serialize_field("code", &self.code())?;));
+		let len = 2 + usize::from(code.is_some());
+		let header = self.impl_header(Some(quote!(serde::Serialize)), &self.error_bounds());
+		quote!
+		{
+			#[automatically_derived]
+			#header
+			{
+				#[allow(unreachable_code)] // when there are no variants
+				fn serialize<S>(&self, serializer: S) -> core::result::Result<S::Ok, S::Error>
+					where S: serde::Serializer
+				{
+					use serde::ser::SerializeStruct;
+
+					let (kind, message): (&str, std::string::String) = match *self
+					{
+						#(#arms),*
+					};
+
+					let mut state = serializer.serialize_struct(#name, #len)?;
+					state.serialize_field("kind", kind)?;
+					state.serialize_field("message", &message)?;
+					#code
+					state.end()
+				}
+			}
+		}
+	}
+
+	/// The implementation of `axum::response::IntoResponse` for this `Error`, which responds with its
+	/// `status_code` and its `Display`, as JSON alongside the name of the variant if it is also
+	/// serialized.
+	fn response(&self) -> TokenStream2
+	{
+		let Self {variants, ..} = self;
+		let cfgs = self.args.entries.iter().map(Entry::cfg_attr);
+		let status = quote!
+		{
+			let status = axum::http::StatusCode::from_u16(self.status_code()).unwrap_or(axum::http::StatusCode::INTERNAL_SERVER_ERROR);
+		};
+
+		let body = match self.args.serialize
+		{
+			true =>
+			{
+				let kinds = variants.iter().map(|v| v.unraw().to_string());
+				quote!
+				{
+					struct Body
+					{
+						error: std::string::String,
+						kind: &'static str,
+					}
+
+					impl serde::Serialize for Body
+					{
+						fn serialize<S>(&self, serializer: S) -> core::result::Result<S::Ok, S::Error>
+							where S: serde::Serializer
+						{
+							use serde::ser::SerializeStruct;
+
+							let mut state = serializer.serialize_struct("Body", 2)?;
+							state.serialize_field("error", &self.error)?;
+							state.serialize_field("kind", self.kind)?;
+							state.end()
+						}
+					}
+
+					let kind = match self
+					{
+						#(#cfgs Self::#variants {..} => #kinds),*
+					};
+
+					axum::Json(Body {error: self.to_string(), kind})
+				}
+			},
+			false => quote!(self.to_string()),
+		};
+
+		let header = self.impl_header(Some(quote!(axum::response::IntoResponse)), &self.error_bounds());
+		quote!
+		{
+			#[automatically_derived]
+			#header
+			{
+				#[allow(unreachable_code)] // when there are no variants
+				fn into_response(self) -> axum::response::Response
+				{
+					#status
+					axum::response::IntoResponse::into_response((status, {#body}))
+				}
+			}
+		}
+	}
+
+	/// The `Display` of the `variant` added by the `option` option.
+	fn unit_display(&self, variant: &Ident) -> String
+	{
+		self.args.option_display.as_ref().map_or_else(|| method_name(variant).replace('_', " "), LitStr::value)
+	}
+
+	/// The name of the fieldless `enum` generated by the `kind` option.
+	pub(crate) fn kind_ident(&self) -> Ident
+	{
+		format_ident!("{}Kind", self.ident)
+	}
+
+	/// The fieldless `enum` generated by the `kind` option, and the method which returns it.
+	fn kind(&self) -> TokenStream2
+	{
+		let Self {ident, variants, vis, ..} = self;
+		let kind = self.kind_ident();
+
+		let doc = format!("The kind of [`{}`], without the `Error` it wraps.", ident);
+		let method_doc = format!("The [`{}`] of this `Error`.", kind);
+		let non_exhaustive = self.args.non_exhaustive.then(|| quote!(#[non_exhaustive]));
+		let variant_docs = variants.iter().map(|v| format!("The kind of [`{}::{}`].", ident, v));
+		let cfgs: Vec<_> = self.args.entries.iter().map(Entry::cfg_attr).collect();
+		let header = self.impl_header(None, &[]);
+
+		let eq_kind = self.args.eq_kind.then(||
+		{
+			let (impl_generics, _, where_clause) = self.generics.split_for_impl();
+			let eq_header = self.impl_header(Some(quote!(core::cmp::PartialEq<#kind>)), &[]);
+			let ty = self.ty();
+			let method = quote!
+			{
+				/// Whether this `Error` and the `other` are the same variant, regardless of the
+				/// `Error` each wraps.
+				#vis fn eq_kind(&self, other: &Self) -> bool
+				{
+					self.kind() == other.kind()
+				}
+			};
+
+			let impls = quote!
+			{
+				#eq_header
+				{
+					fn eq(&self, other: &#kind) -> bool
+					{
+						self.kind() == *other
+					}
+				}
+
+				impl #impl_generics core::cmp::PartialEq<#ty> for #kind #where_clause
+				{
+					fn eq(&self, other: &#ty) -> bool
+					{
+						*self == other.kind()
+					}
+				}
+			};
+
+			(method, impls)
+		});
+		let (eq_method, eq_impls) = eq_kind.unzip();
+
+		quote!
+		{
+			#[doc = #doc]
+			#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+			#non_exhaustive
+			#vis enum #kind
+			{
+				#(#cfgs #[doc = #variant_docs] #variants),*
+			}
+
+			#header
+			{
+				#[doc = #method_doc]
+				#vis fn kind(&self) -> #kind
+				{
+					match *self
+					{
+						#(#cfgs Self::#variants {..} => #kind::#variants),*
+					}
+				}
+
+				#eq_method
+			}
+
+			#eq_impls
+		}
+	}
+}
+
+/// The numeric code of each entry, if any entry has a code (or `display_code` is given).
+///
+/// Like the discriminants of an `enum`, entries without a code are given the code after the one
+/// before them (or 0, if they are first).
+fn codes(args: &Args) -> syn::Result<Option<Vec<u32>>>
+{
+	if !args.display_code && args.entries.iter().all(|e| e.code.is_none())
+	{
+		return Ok(None);
+	}
+
+	let mut codes: Vec<u32> = Vec::with_capacity(args.entries.len());
+	for err in &args.entries
+	{
+		let code = match (&err.code, codes.last())
+		{
+			(Some(code), _) => code.base10_parse()?,
+			(None, None) => 0,
+			(None, Some(last)) => last.checked_add(1).ok_or_else(|| syn::Error::new_spanned(err.culprit(), format!(
+				"the code after {} does not fit in a `u32`; give this entry a `code`", last,
+			)))?,
+		};
+
+		if let Some(first) = codes.iter().position(|c| *c == code)
+		{
+			let culprit = |e: &Entry| e.code.as_ref().map_or_else(|| e.culprit(), |c| c.to_token_stream());
+			let mut error = syn::Error::new_spanned(culprit(err), format!("`code = {}` is used more than once", code));
+			error.combine(syn::Error::new_spanned(culprit(&args.entries[first]), format!("`code = {}` is first used here", code)));
+			return Err(error);
+		}
+
+		codes.push(code);
+	}
+
+	Ok(Some(codes))
+}
+
+/// The `Error` which an `opaque` entry refers to through [`AsRef`].
+fn opaque() -> TokenStream2
+{
+	quote!(dyn std::error::Error + core::marker::Send + core::marker::Sync + 'static)
+}
+
+/// Reject the options which only change the generated `Display`, when the `no_display` option is
+/// given.
+fn check_no_display(args: &Args) -> syn::Result<()>
+{
+	let conflict = [
+		(args.backend == Some(Backend::Thiserror), "backend = \"thiserror\""),
+		(args.display_code, "display_code"),
+		(args.display_prefix.is_some(), "display_prefix"),
+		(args.option_display.is_some(), "option_display"),
+	].iter().find_map(|&(enabled, option)| enabled.then_some(option));
+
+	if let Some(option) = conflict
+	{
+		return Err(syn::Error::new(proc_macro2::Span::call_site(), format!(
+			"`{}` cannot be combined with `no_display`, since it only changes the generated `Display`", option,
+		)));
+	}
+
+	match args.entries.iter().find(|e| e.display.is_some())
+	{
+		Some(entry) => Err(syn::Error::new_spanned(entry.culprit(), "`display` cannot be given to an entry with `no_display`, since it only changes the generated `Display`")),
+		None => Ok(()),
+	}
+}
+
+/// Reject the options and `Error`s which require `std`, when the `no_std` option is given.
+fn check_no_std(args: &Args) -> syn::Result<()>
+{
+	let conflict = [(args.backtrace, "backtrace"), (args.entries.iter().any(|e| e.exit_code.is_some()), "exit_code"), (args.into_io, "into_io"), (args.into_response, "into_response"), (args.dyn_impls, "dyn_impls")]
+		.iter()
+		.find_map(|&(enabled, option)| enabled.then_some(option));
+
+	if let Some(option) = conflict
+	{
+		return Err(syn::Error::new(proc_macro2::Span::call_site(), format!("`{}` cannot be combined with `no_std`, since it requires `std`", option)));
+	}
+
+	// Messages are stored as a `String`, which is rewritten along with the rest of the expansion.
+	let types = args.entries.iter()
+		.flat_map(|e|
+		{
+			let spec = (!matches!(e.spec, Spec::Message(_) | Spec::Unit(_))).then(|| e.spec.to_token_stream());
+			spec.into_iter().chain(e.fields.iter().flatten().map(|f| f.ty.to_token_stream()))
+		})
+		.chain(args.into.iter().map(ToTokens::to_token_stream));
+
+	for ty in types
+	{
+		if mentions_std(ty.clone())
+		{
+			return Err(syn::Error::new_spanned(ty, "this type refers to `std`, which `no_std` cannot use; write it through `core` or `alloc` instead (e.g. `core::fmt::Error`)"));
+		}
+	}
+
+	Ok(())
+}
+
+/// Whether the `tokens` refer to the `std` crate.
+fn mentions_std(tokens: TokenStream2) -> bool
+{
+	tokens.into_iter().any(|token| match token
+	{
+		TokenTree::Group(group) => mentions_std(group.stream()),
+		TokenTree::Ident(ident) => ident == "std",
+		TokenTree::Punct(_) | TokenTree::Literal(_) => false,
+	})
+}
+
+/// The visibility which an item declared in a module within the current one needs, to be as visible
+/// as one with the `vis` declared in the current one.
+fn within(vis: &Visibility) -> Visibility
+{
+	match *vis
+	{
+		Visibility::Inherited => syn::parse_quote!(pub(super)),
+		Visibility::Restricted(ref restricted) =>
+		{
+			let path = &restricted.path;
+			match path.segments.first()
+			{
+				Some(first) if first.ident == "self" && path.segments.len() == 1 => syn::parse_quote!(pub(super)),
+				Some(first) if first.ident == "super" => syn::parse_quote!(pub(in super::#path)),
+				_ => vis.clone(),
+			}
+		},
+		Visibility::Crate(_) | Visibility::Public(_) => vis.clone(),
+	}
+}
+
+/// The `tokens` with each path into `std` replaced by the same path into `core` (or `alloc`, for
+/// the items which require an allocator), for the `no_std` option.
+pub(crate) fn without_std(tokens: TokenStream2) -> TokenStream2
+{
+	let tokens: Vec<_> = tokens.into_iter().collect();
+	let mut replaced = TokenStream2::new();
+	for (i, tt) in tokens.iter().enumerate()
+	{
+		// Paths which are already qualified (e.g. `erro_rs::std`) are left alone.
+		let qualified = i > 1 && matches!(tokens[i - 2], TokenTree::Punct(ref p) if p.as_char() == ':' && p.spacing() == Spacing::Joint);
+		match *tt
+		{
+			TokenTree::Group(ref group) =>
+			{
+				let mut inner = Group::new(group.delimiter(), without_std(group.stream()));
+				inner.set_span(group.span());
+				replaced.extend(Some(TokenTree::Group(inner)));
+			},
+			TokenTree::Ident(ref ident) if ident == "std" && !qualified =>
+			{
+				let krate = match tokens.get(i + 3)
+				{
+					Some(TokenTree::Ident(module)) if ["boxed", "string", "sync", "vec"].iter().any(|m| module == m) => "alloc",
+					_ => "core",
+				};
+
+				replaced.extend(Some(TokenTree::Ident(Ident::new(krate, ident.span()))));
+			},
+			TokenTree::Ident(ref ident) if ident == "format" && !qualified &&
+				matches!(tokens.get(i + 1), Some(TokenTree::Punct(p)) if p.as_char() == '!') =>
+			{
+				replaced.extend(quote_spanned!(ident.span()=> alloc::#ident));
+			},
+			ref other => replaced.extend(Some(other.clone())),
+		}
+	}
+
+	replaced
+}
+
+/// Reject the options which `thiserror` has no equivalent for, when it is the backend.
+fn check_thiserror(args: &Args) -> syn::Result<()>
+{
+	let conflict = [(args.backtrace, "backtrace"), (args.location, "location"), (!args.capture.is_empty(), "capture"), (args.clone.is_some(), "clone")]
+		.iter()
+		.find_map(|&(enabled, option)| enabled.then_some(option));
+
+	if let Some(option) = conflict
+	{
+		return Err(syn::Error::new(proc_macro2::Span::call_site(), format!("`{}` cannot be combined with `backend = \"thiserror\"`", option)));
+	}
+
+	for err in &args.entries
+	{
+		let conflict = [(err.boxed, "boxed"), (err.display == Some(Display::Debug), "display = \"debug\""), (err.fields.is_some(), "fields"), (err.no_bound, "no_bound"), (err.opaque, "opaque")]
+			.iter()
+			.find_map(|&(enabled, option)| enabled.then_some(option));
+
+		if let Some(option) = conflict
+		{
+			return Err(syn::Error::new_spanned(err.culprit(), format!("`{}` cannot be combined with `backend = \"thiserror\"`", option)));
+		}
+	}
+
+	Ok(())
+}
+
+/// Reject `Error`s which are written the same way more than once, and would each implement [`From`].
+fn check_duplicates(errors: &[Entry]) -> syn::Result<()>
+{
+	let mut seen: Vec<(String, &Spec)> = Vec::with_capacity(errors.len());
+	// Messages are distinguished by their aliases instead, and an `Error` can be wrapped by several
+	// variants as long as only one of them implements `From`.
+	for err in errors.iter().filter(|e| e.implements_from()).map(|e| &e.spec).filter(|s| !matches!(s, Spec::Message(_)))
+	{
+		let err_written = written(err);
+		if let Some((_, first)) = seen.iter().find(|(s, _)| *s == err_written)
+		{
+			let mut error = syn::Error::new_spanned(err, format!(
+				"`{}` is listed more than once; give all but one of them `no_from`", err_written,
+			));
+			error.combine(syn::Error::new_spanned(first, format!("`{}` is first listed here", err_written)));
+			return Err(error);
+		}
+
+		seen.push((err_written, err));
+	}
+
+	Ok(())
+}
+
+/// The part of each method generated for the `variant` which refers to it, e.g. `std_io` for
+/// `StdIo`.
+fn method_name(variant: &Ident) -> String
+{
+	variant.unraw().to_string().to_snake_case()
+}
+
+/// A field which every variant stores in addition to the `Error` it wraps.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum Extra
+{
+	/// The backtrace captured by the `backtrace` option.
+	Backtrace,
+
+	/// The location captured by the `location` option.
+	Location,
+
+	/// The arguments captured by the `capture` option.
+	Captures,
+}
+
+impl Extra
+{
+	/// The documentation of the field.
+	fn doc(self) -> &'static str
+	{
+		match self
+		{
+			Self::Backtrace => "The backtrace captured when this `Error` was created.",
+			Self::Location => "Where this `Error` was created.",
+			Self::Captures => "The `Debug` representation of the arguments captured from the function.",
+		}
+	}
+
+	/// The name of the field, when the variant is a struct.
+	fn ident(self) -> Ident
+	{
+		match self
+		{
+			Self::Backtrace => format_ident!("backtrace"),
+			Self::Location => format_ident!("location"),
+			Self::Captures => format_ident!("captures"),
+		}
+	}
+
+	/// The type of the field.
+	fn ty(self, args: &Args) -> TokenStream2
+	{
+		match self
+		{
+			Self::Backtrace => quote!(std::backtrace::Backtrace),
+			Self::Location => quote!(&'static std::panic::Location<'static>),
+			Self::Captures =>
+			{
+				let len = args.capture.len();
+				quote!([std::string::String; #len])
+			},
+		}
+	}
+
+	/// The value of the field when the `Error` is created.
+	fn value(self) -> TokenStream2
+	{
+		match self
+		{
+			Self::Backtrace => quote!(std::backtrace::Backtrace::capture()),
+			Self::Location => quote!(std::panic::Location::caller()),
+			// Filled in when the `Error` is returned from the function.
+			Self::Captures => quote!(std::default::Default::default()),
+		}
+	}
+}
+
+/// A match arm pattern (behind the `cfg` of `err`, if any) which matches the `variant` of `err`,
+/// binding the `Error` it wraps to `binding`.
+fn pattern(err: &Entry, variant: &Ident, binding: TokenStream2) -> TokenStream2
+{
+	let cfg = err.cfg_attr();
+	match err.fields
+	{
+		Some(_) => quote!(#cfg Self::#variant {source: #binding, ..}),
+		None => quote!(#cfg Self::#variant(#binding, ..)),
+	}
+}
+
+/// A match arm pattern (behind the `cfg` of `err`, if any) which matches the `variant` of `err`
+/// without binding anything.
+fn wildcard(err: &Entry, variant: &Ident) -> TokenStream2
+{
+	let cfg = err.cfg_attr();
+	quote!(#cfg Self::#variant {..})
+}
+
+/// An identifier for a method named `name`, which is made raw if `name` is a keyword.
+fn method_ident(name: &str) -> Ident
+{
+	match name
+	{
+		_ if syn::parse_str::<Ident>(name).is_ok() => format_ident!("{}", name),
+		// These keywords cannot be raw identifiers.
+		"crate" | "self" | "super" => format_ident!("{}_", name),
+		_ => Ident::new_raw(name, proc_macro2::Span::call_site()),
+	}
+}
+
+/// Reject variants which would be given the same method names by the `helpers` option, or
+/// constructors.
+fn check_method_collisions(errors: &[Entry], variants: &[Ident]) -> syn::Result<()>
+{
+	let names: Vec<_> = variants.iter().map(method_name).collect();
+	for (i, name) in names.iter().enumerate()
+	{
+		if let Some(first) = names[..i].iter().position(|n| n == name)
+		{
+			let mut error = syn::Error::new_spanned(errors[i].culprit(), format!(
+				"`{}` and `{}` would both be given methods named after `{}`; give one of them a different alias",
+				variants[first], variants[i], name,
+			));
+			error.combine(syn::Error::new_spanned(errors[first].culprit(), format!("`{}` is first used here", name)));
+			return Err(error);
+		}
+	}
+
+	Ok(())
+}
+
+/// Reject `Error`s which would be given the same variant name, reporting each collision.
+fn check_collisions(errors: &[Entry], variants: &[Ident]) -> syn::Result<()>
+{
+	let mut collisions: Option<syn::Error> = None;
+	for (i, variant) in variants.iter().enumerate()
+	{
+		if let Some(first) = variants[..i].iter().position(|v| v == variant)
+		{
+			let (first_err, second_err) = (written(errors[first].spec.source()), written(errors[i].spec.source()));
+			let mut error = syn::Error::new_spanned(errors[i].culprit(), format!(
+				"`{}` and `{}` would both be named `{}`; give one of them an alias, e.g. `{} = \"...\"`",
+				first_err, second_err, variant, second_err,
+			));
+			error.combine(syn::Error::new_spanned(errors[first].culprit(), format!("`{}` is first used here", variant)));
+			match collisions
+			{
+				Some(ref mut collisions) => collisions.combine(error),
+				None => collisions = Some(error),
+			}
+		}
+	}
+
+	collisions.map_or(Ok(()), Err)
+}
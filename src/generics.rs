@@ -0,0 +1,176 @@
+use
+{
+	crate::entry::Entry,
+
+	proc_macro2::{Spacing, TokenStream as TokenStream2, TokenTree},
+	quote::{format_ident, ToTokens},
+	syn::{punctuated::Punctuated, GenericParam, Generics, Ident, WhereClause, WherePredicate},
+};
+
+/// The generic parameters of the function which the `entries` refer to (and those which their
+/// bounds refer to, in turn), alongside those bounds. Each [`Entry`] which refers to any of them is
+/// marked as [`generic`](Entry::generic), and each which refers to any of its lifetimes as
+/// [`borrowed`](Entry::borrowed).
+///
+/// Parameters which are not referred to are left out, so that the generated `Error` does not need
+/// to mark them as used.
+pub(crate) fn used(entries: &mut [Entry], generics: &Generics) -> Generics
+{
+	let params: Vec<_> = generics.type_params().map(|p| &p.ident).collect();
+	let lifetimes: Vec<_> = generics.lifetimes().map(|l| &l.lifetime.ident).collect();
+	let predicates: Vec<_> = generics.where_clause.iter().flat_map(|w| &w.predicates).filter_map(|p| match p
+	{
+		WherePredicate::Type(ty) => Some(ty),
+		_ => None,
+	}).collect();
+
+	let mut used = Vec::new();
+	let mut used_lifetimes = Vec::new();
+	for err in entries.iter_mut()
+	{
+		let mut tokens = err.spec.to_token_stream();
+		err.fields.iter().flatten().for_each(|f| f.ty.to_tokens(&mut tokens));
+
+		let mentioned = mentions(tokens.clone(), &params);
+		err.generic = !mentioned.is_empty();
+		used.extend(mentioned);
+
+		let borrowed = mentions_lifetimes(tokens, &lifetimes);
+		err.borrowed = !borrowed.is_empty();
+		used_lifetimes.extend(borrowed);
+	}
+
+	// The bounds of a parameter may refer to others, e.g. `T: Iterator<Item = U>`.
+	loop
+	{
+		let len = used.len();
+		let bounds = generics.type_params().filter(|p| used.contains(&&p.ident)).map(|p| p.bounds.to_token_stream())
+			.chain(predicates.iter().filter(|p| !mentions(p.bounded_ty.to_token_stream(), &used).is_empty()).map(|p| p.to_token_stream()))
+			.collect::<Vec<_>>();
+
+		for bound in bounds
+		{
+			for param in mentions(bound.clone(), &params)
+			{
+				if !used.contains(&param)
+				{
+					used.push(param);
+				}
+			}
+
+			used_lifetimes.extend(mentions_lifetimes(bound, &lifetimes));
+		}
+
+		if used.len() == len
+		{
+			break;
+		}
+	}
+
+	let params = generics.params.iter().filter(|p| match p
+	{
+		GenericParam::Lifetime(lifetime) => used_lifetimes.contains(&&lifetime.lifetime.ident),
+		GenericParam::Type(ty) => used.contains(&&ty.ident),
+		GenericParam::Const(_) => false,
+	}).cloned().collect();
+
+	let predicates: Punctuated<_, _> = predicates.into_iter()
+		.filter(|p| !mentions(p.bounded_ty.to_token_stream(), &used).is_empty())
+		.map(|p| WherePredicate::Type(p.clone()))
+		.collect();
+
+	Generics
+	{
+		params,
+		where_clause: (!predicates.is_empty()).then(|| WhereClause {where_token: Default::default(), predicates}),
+		..generics.clone()
+	}
+}
+
+/// The `inner` generic parameters (e.g. those of a method) after the `outer` ones which they may
+/// also refer to (e.g. those of its `impl` block), alongside the bounds of both.
+pub(crate) fn merged(outer: Option<&Generics>, inner: &Generics) -> Generics
+{
+	let outer = match outer
+	{
+		Some(outer) => outer,
+		None => return inner.clone(),
+	};
+
+	let predicates: Punctuated<_, _> = outer.where_clause.iter().chain(&inner.where_clause).flat_map(|w| w.predicates.clone()).collect();
+	Generics
+	{
+		params: outer.params.iter().chain(&inner.params).cloned().collect(),
+		where_clause: (!predicates.is_empty()).then(|| WhereClause {where_token: Default::default(), predicates}),
+		..inner.clone()
+	}
+}
+
+/// The `params` which are referred to by the `tokens`, in the order they are first referred to.
+///
+/// Identifiers which come after a `::` are the segments of other paths (e.g. `io::T`), rather
+/// than parameters.
+pub(crate) fn mentions<'a>(tokens: TokenStream2, params: &[&'a Ident]) -> Vec<&'a Ident>
+{
+	let mut found = Vec::new();
+	let mut colons = 0;
+	for tt in tokens
+	{
+		match tt
+		{
+			TokenTree::Group(ref group) => found.extend(mentions(group.stream(), params)),
+			TokenTree::Ident(ref ident) if colons < 2 => found.extend(params.iter().find(|p| *p == &ident)),
+			_ => (),
+		}
+
+		colons = match tt
+		{
+			TokenTree::Punct(ref punct) if punct.as_char() == ':' && (colons == 0 && punct.spacing() == Spacing::Joint || colons == 1) => colons + 1,
+			_ => 0,
+		};
+	}
+
+	let mut unique = Vec::with_capacity(found.len());
+	for param in found
+	{
+		if !unique.contains(&param)
+		{
+			unique.push(param);
+		}
+	}
+
+	unique
+}
+
+/// The `lifetimes` (by their names, without the `'`) which are referred to by the `tokens`.
+pub(crate) fn mentions_lifetimes<'a>(tokens: TokenStream2, lifetimes: &[&'a Ident]) -> Vec<&'a Ident>
+{
+	let mut found = Vec::new();
+	let mut quoted = false;
+	for tt in tokens
+	{
+		match tt
+		{
+			TokenTree::Group(ref group) => found.extend(mentions_lifetimes(group.stream(), lifetimes)),
+			TokenTree::Ident(ref ident) if quoted => found.extend(lifetimes.iter().find(|l| *l == &ident)),
+			_ => (),
+		}
+
+		quoted = matches!(tt, TokenTree::Punct(ref punct) if punct.as_char() == '\'' && punct.spacing() == Spacing::Joint);
+	}
+
+	found
+}
+
+/// An identifier starting with `name` which is not the name of any of the `generics`, for the
+/// generic parameters which the macro adds alongside them.
+pub(crate) fn fresh(name: &str, generics: &Generics) -> Ident
+{
+	let mut name = name.to_owned();
+	while generics.type_params().any(|p| p.ident == name)
+	{
+		name.push('_');
+	}
+
+	format_ident!("{}", name)
+}
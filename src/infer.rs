@@ -0,0 +1,322 @@
+use
+{
+	crate::{entry::{Entry, Spec}, split_result, written},
+
+	proc_macro2::{TokenStream as TokenStream2, TokenTree},
+	quote::ToTokens,
+	syn::{visit::{self, Visit}, Block, Expr, ExprAsync, ExprClosure, ExprTry, GenericMethodArgument, Ident, Item, Macro, Path, Type},
+};
+
+/// The functions whose `Error` is known, by the last two segments of their path.
+const FUNCTIONS: &[(&str, &str)] = &[
+	("File::create", "std::io::Error"),
+	("File::open", "std::io::Error"),
+	("String::from_utf8", "std::string::FromUtf8Error"),
+	("env::current_dir", "std::io::Error"),
+	("env::set_current_dir", "std::io::Error"),
+	("env::var", "std::env::VarError"),
+	("fs::canonicalize", "std::io::Error"),
+	("fs::copy", "std::io::Error"),
+	("fs::create_dir", "std::io::Error"),
+	("fs::create_dir_all", "std::io::Error"),
+	("fs::hard_link", "std::io::Error"),
+	("fs::metadata", "std::io::Error"),
+	("fs::read", "std::io::Error"),
+	("fs::read_dir", "std::io::Error"),
+	("fs::read_link", "std::io::Error"),
+	("fs::read_to_string", "std::io::Error"),
+	("fs::remove_dir", "std::io::Error"),
+	("fs::remove_dir_all", "std::io::Error"),
+	("fs::remove_file", "std::io::Error"),
+	("fs::rename", "std::io::Error"),
+	("fs::set_permissions", "std::io::Error"),
+	("fs::symlink_metadata", "std::io::Error"),
+	("fs::write", "std::io::Error"),
+	("str::from_utf8", "std::str::Utf8Error"),
+];
+
+/// The methods whose `Error` is known, by their name.
+const METHODS: &[(&str, &str)] = &[
+	("duration_since", "std::time::SystemTimeError"),
+	("flush", "std::io::Error"),
+	("read_exact", "std::io::Error"),
+	("read_line", "std::io::Error"),
+	("read_to_end", "std::io::Error"),
+	("read_to_string", "std::io::Error"),
+	("sync_all", "std::io::Error"),
+	("write_all", "std::io::Error"),
+];
+
+/// The `Error` of `str::parse` for each type it is known to parse into.
+const PARSED: &[(&[&str], &str)] = &[
+	(&["i8", "i16", "i32", "i64", "i128", "isize", "u8", "u16", "u32", "u64", "u128", "usize"], "std::num::ParseIntError"),
+	(&["f32", "f64"], "std::num::ParseFloatError"),
+	(&["bool"], "std::str::ParseBoolError"),
+	(&["char"], "std::char::ParseCharError"),
+	(&["IpAddr", "Ipv4Addr", "Ipv6Addr", "SocketAddr", "SocketAddrV4", "SocketAddrV6"], "std::net::AddrParseError"),
+];
+
+/// The entries for the `Error`s propagated by each `?` in the `block` which can be recognized, and
+/// the `?`s which cannot, given whether the `?`s of `async` blocks propagate from the function too.
+pub(crate) fn entries(block: &Block, through_async: bool) -> (Vec<Entry>, Vec<ExprTry>)
+{
+	let mut locals = Locals::default();
+	locals.visit_block(block);
+
+	let mut visitor = Visitor {errors: Vec::new(), hidden: false, locals: locals.0, through_async, unknown: Vec::new()};
+	visitor.visit_block(block);
+
+	let mut entries: Vec<Entry> = Vec::with_capacity(visitor.errors.len());
+	for err in visitor.errors
+	{
+		if !entries.iter().any(|e| written(&e.spec) == written(&err))
+		{
+			entries.push(Entry::found(err));
+		}
+	}
+
+	(entries, visitor.unknown)
+}
+
+/// The `entries` which the `strict` option can prove are never propagated by a `?` in the `block`,
+/// given the names of the generated `Error` and its `Result` alias (which may be used to construct or
+/// convert into it directly).
+///
+/// Nothing is reported unless every `?` in the `block` is recognized and matches one of the
+/// `entries`, and nothing else could convert into the generated `Error`.
+pub(crate) fn unused<'e>(block: &Block, through_async: bool, entries: &'e [Entry], names: &[&Ident]) -> Vec<&'e Entry>
+{
+	if mentions(block.to_token_stream(), names)
+	{
+		return Vec::new();
+	}
+
+	let mut locals = Locals::default();
+	locals.visit_block(block);
+
+	let mut visitor = Visitor {errors: Vec::new(), hidden: false, locals: locals.0, through_async, unknown: Vec::new()};
+	visitor.visit_block(block);
+	if visitor.hidden || !visitor.unknown.is_empty()
+	{
+		return Vec::new();
+	}
+
+	let mut used = vec![false; entries.len()];
+	for err in &visitor.errors
+	{
+		let mut matched = false;
+		for (i, entry) in entries.iter().enumerate()
+		{
+			if segments(&entry.spec).is_some_and(|e| e.matches(err))
+			{
+				used[i] = true;
+				matched = true;
+			}
+		}
+
+		// The `Error` may have been imported under another name.
+		if !matched
+		{
+			return Vec::new();
+		}
+	}
+
+	entries.iter().zip(used)
+		.filter(|(e, used)| !used && e.implements_from() && !e.from_set && e.flattened.is_none() && segments(&e.spec).is_some())
+		.map(|(e, _)| e)
+		.collect()
+}
+
+/// Whether the `tokens` refer to a conversion or to any of the `names`.
+fn mentions(tokens: TokenStream2, names: &[&Ident]) -> bool
+{
+	tokens.into_iter().any(|token| match token
+	{
+		TokenTree::Group(group) => mentions(group.stream(), names),
+		TokenTree::Ident(ident) => names.iter().any(|n| ident == **n) ||
+			matches!(ident.to_string().as_str(), "from" | "into" | "try_from" | "try_into"),
+		TokenTree::Punct(_) | TokenTree::Literal(_) => false,
+	})
+}
+
+/// Whether the `tokens` contain a `?`.
+fn propagates(tokens: TokenStream2) -> bool
+{
+	tokens.into_iter().any(|token| match token
+	{
+		TokenTree::Group(group) => propagates(group.stream()),
+		TokenTree::Punct(punct) => punct.as_char() == '?',
+		TokenTree::Ident(_) | TokenTree::Literal(_) => false,
+	})
+}
+
+/// The segments of the path to the `Error` written as the `spec`, if it is a plain path.
+fn segments(spec: &Spec) -> Option<Segments>
+{
+	let path = match *spec
+	{
+		Spec::Path(ref path) => path,
+		Spec::Str(_, ref ty) => match **ty
+		{
+			Type::Path(ref ty) if ty.qself.is_none() => &ty.path,
+			_ => return None,
+		},
+		Spec::Message(_) | Spec::Qualified(_) | Spec::Unit(_) => return None,
+	};
+
+	Some(Segments::new(path))
+}
+
+/// The names of the segments of a path, without their generic arguments.
+struct Segments(Vec<String>);
+
+impl Segments
+{
+	/// The segments of the `path`.
+	fn new(path: &Path) -> Self
+	{
+		Self(path.segments.iter().map(|s| s.ident.to_string()).collect())
+	}
+
+	/// Whether these segments and the path of the `ty` could refer to the same `Error`, because one
+	/// ends the other (e.g. `io::Error` and `std::io::Error`).
+	fn matches(&self, ty: &Type) -> bool
+	{
+		match *ty
+		{
+			Type::Path(ref ty) if ty.qself.is_none() =>
+			{
+				let other = Self::new(&ty.path);
+				other.0.ends_with(&self.0) || self.0.ends_with(&other.0)
+			},
+			_ => false,
+		}
+	}
+}
+
+/// The functions declared within the body of a function which return a `Result`, alongside the
+/// `Error` of that `Result`.
+#[derive(Default)]
+struct Locals(Vec<(String, Type)>);
+
+impl<'ast> Visit<'ast> for Locals
+{
+	fn visit_item(&mut self, item: &'ast Item)
+	{
+		if let Item::Fn(ref function) = *item
+		{
+			if let syn::ReturnType::Type(_, ref ty) = function.sig.output
+			{
+				if let Some((_, err)) = split_result(ty)
+				{
+					self.0.push((function.sig.ident.to_string(), err));
+				}
+			}
+		}
+	}
+}
+
+/// Finds the `Error` propagated by each `?` in the body of a function.
+struct Visitor
+{
+	/// The `Error`s which were recognized, in order.
+	errors: Vec<Type>,
+
+	/// Whether a `?` was found which was not visited, e.g. within a closure or macro.
+	hidden: bool,
+
+	/// The functions declared within the body, and the `Error`s they return.
+	locals: Vec<(String, Type)>,
+
+	/// Whether the `?`s of `async` blocks propagate from the function.
+	through_async: bool,
+
+	/// The `?`s whose `Error` was not recognized.
+	unknown: Vec<ExprTry>,
+}
+
+impl Visitor
+{
+	/// The `Error` propagated when `?` is applied to the `expr`, if it is known.
+	fn classify(&self, expr: &Expr) -> Option<Type>
+	{
+		let err = match *expr
+		{
+			Expr::Group(ref group) => return self.classify(&group.expr),
+			Expr::Paren(ref paren) => return self.classify(&paren.expr),
+			Expr::Call(ref call) => match *call.func
+			{
+				Expr::Path(ref path) =>
+				{
+					let segments: Vec<_> = path.path.segments.iter().map(|s| s.ident.to_string()).collect();
+					if let [ref name] = segments[..]
+					{
+						if let Some((_, err)) = self.locals.iter().find(|(local, _)| local == name)
+						{
+							return Some(err.clone());
+						}
+					}
+
+					let tail = segments[segments.len().saturating_sub(2)..].join("::");
+					FUNCTIONS.iter().find(|(function, _)| *function == tail).map(|(_, err)| *err)?
+				},
+				_ => return None,
+			},
+			Expr::MethodCall(ref call) if call.method == "parse" =>
+			{
+				let ty = match call.turbofish.as_ref().and_then(|t| t.args.first())
+				{
+					Some(GenericMethodArgument::Type(Type::Path(ref ty))) => ty.path.segments.last()?.ident.to_string(),
+					_ => return None,
+				};
+
+				PARSED.iter().find(|(types, _)| types.contains(&ty.as_str())).map(|(_, err)| *err)?
+			},
+			Expr::MethodCall(ref call) => METHODS.iter().find(|(method, _)| call.method == method).map(|(_, err)| *err)?,
+			_ => return None,
+		};
+
+		Some(syn::parse_str(err).expect("each known `Error` should be a valid type"))
+	}
+}
+
+impl<'ast> Visit<'ast> for Visitor
+{
+	fn visit_expr_async(&mut self, expr: &'ast ExprAsync)
+	{
+		match self.through_async
+		{
+			true => visit::visit_expr_async(self, expr),
+			false => self.hidden |= propagates(expr.block.to_token_stream()),
+		}
+	}
+
+	// The `?`s of a closure propagate from the closure instead.
+	fn visit_expr_closure(&mut self, expr: &'ast ExprClosure)
+	{
+		self.hidden |= propagates(expr.body.to_token_stream());
+	}
+
+	fn visit_expr_try(&mut self, expr: &'ast ExprTry)
+	{
+		match self.classify(&expr.expr)
+		{
+			Some(err) => self.errors.push(err),
+			None => self.unknown.push(expr.clone()),
+		}
+
+		visit::visit_expr_try(self, expr);
+	}
+
+	// The `?`s of a nested function propagate from that function instead.
+	fn visit_item(&mut self, item: &'ast Item)
+	{
+		self.hidden |= propagates(item.to_token_stream());
+	}
+
+	// The contents of a macro cannot be parsed.
+	fn visit_macro(&mut self, mac: &'ast Macro)
+	{
+		self.hidden |= propagates(mac.tokens.clone());
+	}
+}
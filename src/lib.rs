@@ -1,11 +1,30 @@
+mod args;
+mod defer;
+mod entry;
+mod expand;
+mod generics;
+mod infer;
+mod scope;
+mod set;
+mod standalone;
+mod wrap;
+
 use
 {
-	std::collections::HashMap,
+	args::Args,
+	defer::Emit,
+	entry::{Entry, Spec},
+	expand::ErrorEnum,
+	scope::Scope,
+	set::ErrorSet,
+	standalone::Standalone,
+	wrap::Wrap,
+
 	proc_macro::TokenStream,
+	proc_macro2::{Span, TokenStream as TokenStream2, TokenTree},
 
-	heck::CamelCase,
-	quote::{format_ident, quote},
-	syn::{AttributeArgs, ItemFn, Lit, Meta, NestedMeta, Path, punctuated::Punctuated, ReturnType, token::Paren, Type::Tuple, TypeTuple},
+	quote::{format_ident, quote, quote_spanned, ToTokens},
+	syn::{Attribute, Block, FnArg, GenericArgument, Ident, Item, LitStr, Pat, PathArguments, punctuated::Punctuated, ReturnType, Signature, spanned::Spanned, token::Paren, Type, Type::Tuple, TypeTuple, Visibility},
 };
 
 /// # Summary
@@ -24,123 +43,4374 @@ use
 /// > ## Note
 /// >
 /// > This macro uses the paths to each `Error` to determine the name of the generated variants.
-/// > That is to say `std::io::Error` will map to `StdIo`, and `io::Error` will map to `Io`.
+/// > That is to say `std::io::Error` will map to `StdIo`, and `io::Error` will map to `Io`. Only a
+/// > trailing "Error" on the last segment is removed, so `std::num::ParseIntError` will map to
+/// > `StdNumParseInt` and `openssl::error::ErrorStack` will map to `OpensslErrorErrorStack`.
+/// > A leading `::` is ignored, so `::std::io::Error` also maps to `StdIo`:
+/// >
+/// > ```
+/// > use erro_rs::errors;
+/// >
+/// > #[errors(::serde_json::Error, ::std::io::Error)]
+/// > fn read_json(path: &str) -> serde_json::Value {
+/// >     let content = std::fs::read_to_string(path)?;
+/// >     Ok(serde_json::from_str(&content)?)
+/// > }
+/// >
+/// > let _: ::std::io::Error = match read_json("/this/path/does/not/exist") {
+/// >     Err(ReadJsonError::StdIo(e)) => e,
+/// >     Err(ReadJsonError::SerdeJson(e)) => panic!("{}", e),
+/// >     Ok(_) => unreachable!(),
+/// > };
+/// > ```
+/// >
+/// > `crate`, `self`, and `super` are ignored as well, so `crate::db::Error` will map to `Db`.
+/// > Every other segment is converted to `CamelCase` on its own (without the `r#` of a raw
+/// > identifier), and those which are left empty are skipped:
+/// >
+/// > ```
+/// > use erro_rs::errors;
+/// >
+/// > mod my_crate {
+/// >     pub mod parse_error {
+/// >         pub type ParseError = std::fmt::Error;
+/// >     }
+/// >
+/// >     pub mod r#type {
+/// >         pub type Error = std::num::ParseIntError;
+/// >     }
+/// > }
+/// >
+/// > #[errors(
+/// >     serde_json::error::Error,
+/// >     std::io::Error,
+/// >     my_crate::parse_error::ParseError,
+/// >     my_crate::r#type::Error,
+/// > )]
+/// > fn foo() {
+/// >     Ok(())
+/// > }
+/// >
+/// > let _ = |e: FooError| match e {
+/// >     FooError::SerdeJsonError(_) => "serde_json::error::Error",
+/// >     FooError::StdIo(_) => "std::io::Error",
+/// >     FooError::MyCrateParseErrorParse(_) => "my_crate::parse_error::ParseError",
+/// >     FooError::MyCrateType(_) => "my_crate::r#type::Error",
+/// > };
+/// > ```
+///
+/// You can override the default naming by assigning the `Error` an alias, which may be written as
+/// an identifier or a string containing one, after `=` or `as`:
+///
+/// ```ignore
+/// #[errors(bincode::Error = Bincode, std::io::Error = "IoError", std::fmt::Error as Fmt)]
+/// ```
+///
+/// If no `Error`s are passed, the generated `Error` has no variants and can never be returned. The
+/// return type of the function is still rewritten, so `Error`s can be added later without breaking
+/// its callers:
+///
+/// ```
+/// use erro_rs::errors;
+///
+/// #[errors()]
+/// fn answer() -> u8 {
+///     Ok(42)
+/// }
+///
+/// let result: Result<u8, AnswerError> = answer();
+/// assert_eq!(result.unwrap(), 42);
+/// ```
+///
+/// Each `Error` may only be listed once, even if it is given an alias:
+///
+/// ```compile_fail
+/// use erro_rs::errors;
+///
+/// #[errors(std::io::Error, std::io::Error)]
+/// fn foo() {
+///     Ok(())
+/// }
+/// ```
+///
+/// ```compile_fail
+/// use erro_rs::errors;
+///
+/// #[errors(std::io::Error, std::io::Error = "Io")]
+/// fn foo() {
+///     Ok(())
+/// }
+/// ```
+///
+/// Likewise, no two `Error`s may be given the same variant name, whether it was derived from their
+/// paths or assigned as an alias:
+///
+/// ```compile_fail
+/// use erro_rs::errors;
+///
+/// mod std_io {
+///     pub type Error = std::io::Error;
+/// }
+///
+/// // Both are named `StdIo`
+/// #[errors(std::io::Error, std_io::Error)]
+/// fn foo() {
+///     Ok(())
+/// }
+/// ```
+///
+/// ```compile_fail
+/// use erro_rs::errors;
+///
+/// #[errors(std::io::Error, std::fmt::Error = "StdIo")]
+/// fn foo() {
+///     Ok(())
+/// }
+/// ```
+///
+/// `Error`s which cannot be written as a plain path, such as those with generic arguments, can be
+/// passed as a string instead. Unless it is given an alias, the variant for such an `Error` is named
+/// after the last segment of its path:
+///
+/// ```
+/// use std::sync::mpsc::{self, SendError};
+/// use erro_rs::errors;
+///
+/// #[errors("std::sync::mpsc::SendError<String>", std::io::Error)]
+/// fn greet(tx: &mpsc::Sender<String>) {
+///     tx.send("hello".into())?;
+///     Ok(())
+/// }
+///
+/// let (tx, rx) = mpsc::channel();
+/// drop(rx);
+///
+/// assert!(matches!(greet(&tx), Err(GreetError::Send(SendError(_)))));
+/// ```
+///
+/// The same `Error` may be passed more than once with different generic arguments, each of which
+/// is given its own variant. Since their variants would be named the same, they must be given
+/// aliases (or see [`auto_disambiguate`](#auto_disambiguate)):
+///
+/// ```
+/// use std::sync::mpsc::{self, SendError};
+/// use erro_rs::errors;
+///
+/// struct Command;
+/// struct Event;
+///
+/// #[errors("mpsc::SendError<Command>" = "SendCommand", "mpsc::SendError<Event>" = "SendEvent")]
+/// fn dispatch(commands: &mpsc::Sender<Command>, events: &mpsc::Sender<Event>) {
+///     events.send(Event)?;
+///     commands.send(Command)?;
+///     Ok(())
+/// }
+///
+/// let (commands, _) = mpsc::channel();
+/// let (events, rx) = mpsc::channel();
+/// assert!(matches!(dispatch(&commands, &events), Err(DispatchError::SendCommand(SendError(Command)))));
+///
+/// drop(rx);
+/// assert!(matches!(dispatch(&commands, &events), Err(DispatchError::SendEvent(SendError(Event)))));
+/// ```
+///
+/// ```compile_fail
+/// use std::sync::mpsc;
+/// use erro_rs::errors;
+///
+/// #[errors("mpsc::SendError<u8>", "mpsc::SendError<u16>")]
+/// fn foo() {
+///     Ok(())
+/// }
+/// ```
+///
+/// Boxed trait objects can be passed the same way, though they must be given an alias:
+///
+/// ```
+/// use std::error::Error;
+/// use erro_rs::errors;
+///
+/// type Callback = fn() -> Result<(), Box<dyn Error + Send + Sync>>;
+///
+/// #[errors("Box<dyn std::error::Error + Send + Sync>" = "Callback", std::io::Error)]
+/// fn call(callback: Callback) {
+///     callback()?;
+///     Ok(())
+/// }
+///
+/// let err = call(|| Err("oh no".into())).unwrap_err();
+/// assert!(matches!(err, CallError::Callback(_)));
+/// assert_eq!(err.source().unwrap().to_string(), "oh no");
+/// ```
+///
+/// ```compile_fail
+/// use erro_rs::errors;
+///
+/// #[errors("Box<dyn std::error::Error + Send + Sync>")]
+/// fn foo() {
+///     Ok(())
+/// }
+/// ```
+///
+/// An alias is also required when no name can be derived from the path of an `Error`:
+///
+/// ```
+/// use erro_rs::errors;
+///
+/// mod db {
+///     pub type Error = std::io::Error;
+/// }
+///
+/// type Error = std::fmt::Error;
+///
+/// #[errors(crate::db::Error, self::Error = "Fmt")]
+/// fn query() {
+///     Ok(())
+/// }
+///
+/// fn main() {
+///     assert!(matches!(query(), Ok(()) | Err(QueryError::Db(_) | QueryError::Fmt(_))));
+/// }
+/// ```
+///
+/// ```compile_fail
+/// use erro_rs::errors;
+///
+/// #[errors(crate::Error)]
+/// fn foo() {
+///     Ok(())
+/// }
+/// ```
+///
+/// `Error`s may refer to the generic parameters of the function, in which case the generated
+/// `Error` is given those parameters (and their bounds), but no others. [`From`] cannot be
+/// implemented for such an `Error`, since it may be any type (including the generated `Error`
+/// itself), so it must be converted with the variant instead:
+///
+/// ```
+/// use std::str::FromStr;
+/// use erro_rs::errors;
+///
+/// #[errors(T::Err = "Parse", std::io::Error)]
+/// fn parse_all<T: FromStr, R: std::io::Read>(mut reader: R) -> Vec<T>
+/// where
+///     T::Err: std::error::Error + 'static,
+/// {
+///     let mut s = String::new();
+///     reader.read_to_string(&mut s)?;
+///     s.split_whitespace().map(|s| s.parse().map_err(ParseAllError::Parse)).collect()
+/// }
+///
+/// fn main() {
+///     assert_eq!(parse_all::<i32, _>("1 2 3".as_bytes()).unwrap(), [1, 2, 3]);
+///
+///     let err: ParseAllError<i32> = parse_all("1 x 3".as_bytes()).unwrap_err();
+///     assert_eq!(err.to_string(), "invalid digit found in string");
+/// }
+/// ```
+///
+/// Likewise, `Error`s may borrow with the lifetimes of the function, in which case the generated
+/// `Error` is given those lifetimes. [`From`] is still implemented, but the variant's `source` is
+/// `None`, since a `source` must be `'static`:
+///
+/// ```
+/// use std::{error::Error, fmt};
+/// use erro_rs::errors;
+///
+/// #[derive(Debug)]
+/// struct Unexpected<'input>(&'input str);
+///
+/// impl fmt::Display for Unexpected<'_> {
+///     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+///         write!(f, "unexpected `{}`", self.0)
+///     }
+/// }
+///
+/// impl Error for Unexpected<'_> {}
+///
+/// #[errors("Unexpected<'input>", std::num::ParseIntError)]
+/// fn first<'input>(s: &'input str) -> u8 {
+///     let word = s.split_whitespace().next().unwrap_or(s);
+///     if !word.starts_with(|c: char| c.is_ascii_digit()) {
+///         return Err(Unexpected(word).into());
+///     }
+///
+///     Ok(word.parse()?)
+/// }
+///
+/// fn main() {
+///     let input = String::from("x 2 3");
+///     let err: FirstError<'_> = first(&input).unwrap_err();
+///     assert!(matches!(err, FirstError::Unexpected(Unexpected("x"))));
+///     assert!(err.source().is_none());
+///     assert!(first("1x").unwrap_err().source().is_some());
+/// }
+/// ```
+///
+/// Qualified paths are accepted too, though they must be given an alias. Like generic `Error`s,
+/// [`From`] cannot be implemented for them:
+///
+/// ```
+/// use std::{convert::TryFrom, str::FromStr};
+/// use erro_rs::errors;
+///
+/// #[errors(<T as FromStr>::Err = "Parse", <u8 as TryFrom<i32>>::Error(boxed) = "Range")]
+/// fn parse_byte<T: FromStr + Into<i32>>(s: &str) -> u8
+/// where
+///     T::Err: std::error::Error + 'static,
+/// {
+///     let int = s.parse::<T>().map_err(ParseByteError::Parse)?.into();
+///     u8::try_from(int).map_err(|e| ParseByteError::Range(Box::new(e)))
+/// }
+///
+/// fn main() {
+///     assert_eq!(parse_byte::<i16>("255").unwrap(), 255);
+///     assert!(matches!(parse_byte::<i16>("x"), Err(ParseByteError::Parse(_))));
+///     assert!(matches!(parse_byte::<i16>("256"), Err(ParseByteError::Range(_))));
+/// }
+/// ```
+///
+/// ```compile_fail
+/// use std::str::FromStr;
+/// use erro_rs::errors;
+///
+/// #[errors(<i32 as FromStr>::Err)]
+/// fn foo() {
+///     Ok(())
+/// }
+/// ```
+///
+/// An alias must be a valid identifier. Raw identifiers are accepted:
+///
+/// ```
+/// use erro_rs::errors;
+///
+/// #[errors(std::io::Error = "r#type", std::fmt::Error as r#loop, std::num::ParseIntError = Parse)]
+/// fn foo() {
+///     Ok(())
+/// }
+///
+/// assert!(matches!(foo(), Ok(()) | Err(FooError::r#type(_) | FooError::r#loop(_) | FooError::Parse(_))));
+/// ```
+///
+/// ```compile_fail
+/// use erro_rs::errors;
+///
+/// #[errors(std::io::Error = type)]
+/// fn foo() {
+///     Ok(())
+/// }
+/// ```
+///
+/// ```compile_fail
+/// use erro_rs::errors;
+///
+/// #[errors(std::io::Error = "io error")]
+/// fn foo() {
+///     Ok(())
+/// }
+/// ```
+///
+/// ```compile_fail
+/// use erro_rs::errors;
+///
+/// #[errors(std::io::Error = 3)]
+/// fn foo() {
+///     Ok(())
+/// }
+/// ```
+///
+/// ```compile_fail
+/// use erro_rs::errors;
+///
+/// #[errors("std::sync::mpsc::SendError<")]
+/// fn foo() {
+///     Ok(())
+/// }
+/// ```
+///
+/// Anything else passed to the macro is rejected rather than ignored, such as a literal, a path with
+/// generic arguments (which must be written as a string instead), an unknown option for an entry, or
+/// a lowercase identifier which is not an option (which is most likely a misspelled one; an `Error`
+/// named like this must be written as a longer path, e.g. `self::error`):
+///
+/// ```compile_fail
+/// use erro_rs::errors;
+///
+/// #[errors(std::io::Error, 5)]
+/// fn foo() {
+///     Ok(())
+/// }
+/// ```
+///
+/// ```compile_fail
+/// use erro_rs::errors;
+///
+/// #[errors(std::sync::mpsc::SendError<String>)]
+/// fn foo() {
+///     Ok(())
+/// }
+/// ```
+///
+/// ```compile_fail
+/// use erro_rs::errors;
+///
+/// #[errors(std::io::Error(Io))]
+/// fn foo() {
+///     Ok(())
+/// }
+/// ```
+///
+/// ```compile_fail
+/// use erro_rs::errors;
+///
+/// #[errors(helper, std::io::Error)]
+/// fn foo() {
+///     Ok(())
+/// }
+/// ```
+///
+/// ```compile_fail
+/// use erro_rs::errors;
+///
+/// #[errors(std::io::Error std::fmt::Error)]
+/// fn foo() {
+///     Ok(())
+/// }
+/// ```
+///
+/// # Options
+///
+/// Options may be passed to the macro alongside the `Error`s, in any order. An `Error` which shares
+/// its name with an option must be written as a longer path (e.g. `self::naming`).
+///
+/// ## `auto_disambiguate`
+///
+/// When the derived names of two or more variants collide, the shortest of those names are
+/// lengthened by one segment of their paths (from right to left), repeating until no names collide
+/// or none of the colliding names can be lengthened any further. Aliases are never lengthened.
+///
+/// This is mostly useful alongside `naming = "last"`:
+///
+/// ```
+/// use erro_rs::errors;
+///
+/// mod utf8 {
+///     pub type Error = std::string::FromUtf8Error;
+/// }
+///
+/// // `std::str::Utf8Error` and `crate::utf8::Error` would both be named `Utf8`
+/// #[errors(naming = "last", auto_disambiguate, std::str::Utf8Error, crate::utf8::Error)]
+/// fn decode(bytes: &[u8]) -> String {
+///     std::str::from_utf8(bytes)?;
+///     Ok(String::from_utf8(bytes.to_vec())?)
+/// }
+///
+/// fn main() {
+///     assert!(matches!(decode(&[0xff]), Err(DecodeError::StrUtf8(_))));
+///     assert!(matches!(DecodeError::from(String::from_utf8(vec![0xff]).unwrap_err()), DecodeError::Utf8(_)));
+/// }
+/// ```
+///
+/// Names which still collide are reported once the others have been lengthened:
+///
+/// ```compile_fail
+/// use erro_rs::errors;
+///
+/// mod fmt {
+///     pub type Error = std::num::ParseIntError;
+/// }
+///
+/// mod utf8 {
+///     pub type Error = std::string::FromUtf8Error;
+/// }
+///
+/// // `crate::fmt::Error` cannot be lengthened past the alias of `std::fmt::Error`, but
+/// // `std::str::Utf8Error` and `crate::utf8::Error` are still named `StrUtf8` and `Utf8`
+/// #[errors(naming = "last", auto_disambiguate, std::fmt::Error = "Fmt", crate::fmt::Error, std::str::Utf8Error, crate::utf8::Error)]
+/// fn foo() {
+///     Ok(())
+/// }
+/// ```
+///
+/// The generic arguments of an `Error` passed as a string are added to its name before any more of
+/// its path, e.g. `"mpsc::SendError<Command>"` and `"mpsc::SendError<Event>"` are named
+/// `SendCommand` and `SendEvent`:
+///
+/// ```
+/// use std::sync::mpsc;
+/// use erro_rs::errors;
+///
+/// struct Command;
+/// struct Event;
+///
+/// #[errors(auto_disambiguate, "mpsc::SendError<Command>", "mpsc::SendError<Event>")]
+/// fn dispatch(commands: &mpsc::Sender<Command>, events: &mpsc::Sender<Event>) {
+///     commands.send(Command)?;
+///     Ok(events.send(Event)?)
+/// }
+///
+/// let (commands, rx) = mpsc::channel();
+/// let (events, _) = mpsc::channel();
+/// drop(rx);
+/// assert!(matches!(dispatch(&commands, &events), Err(DispatchError::SendCommand(_))));
+/// ```
+///
+/// ## `backend`
+///
+/// With `backend = "thiserror"`, the generated `Error` derives `thiserror::Error`, and each variant
+/// is given the `#[error]`, `#[from]`, or `#[source]` attributes which implement `Display`,
+/// [`Error`](std::error::Error), and [`From`] as this macro otherwise would. Only the conversions
+/// of messages (which are not `Error`s) are still generated:
+///
+/// ```
+/// use erro_rs::errors;
+///
+/// #[errors(backend = "thiserror", display_prefix, std::io::Error, std::num::ParseIntError(transparent), message)]
+/// fn read_int(path: &str) -> i128 {
+///     Ok(std::fs::read_to_string(path)?.trim().parse()?)
+/// }
+///
+/// #[errors(display_prefix = "read_int", std::io::Error, std::num::ParseIntError(transparent), message)]
+/// fn read_int_by_hand(path: &str) -> i128 {
+///     Ok(std::fs::read_to_string(path)?.trim().parse()?)
+/// }
+///
+/// let path = std::env::temp_dir().join("erro-rs-backend");
+/// std::fs::write(&path, "foo").unwrap();
+///
+/// for path in ["/this/path/does/not/exist", path.to_str().unwrap()] {
+///     let (err, by_hand) = (read_int(path).unwrap_err(), read_int_by_hand(path).unwrap_err());
+///     assert_eq!(err.to_string(), by_hand.to_string());
+///     assert_eq!(std::error::Error::source(&err).is_some(), std::error::Error::source(&by_hand).is_some());
+/// }
+///
+/// assert_eq!(ReadIntError::from("foo").to_string(), ReadIntByHandError::from("foo").to_string());
+/// assert!(matches!(ReadIntError::from(std::io::Error::other("foo")), ReadIntError::StdIo(_)));
+/// ```
+///
+/// The crate which uses the option must also depend on `thiserror`. Unlike the `Display` of this
+/// macro, that of `thiserror` does not write the sources of the `Error` in the alternate form
+/// (`{:#}`). It cannot be combined with `backtrace`, `capture`, `clone`, or `location`, nor with
+/// entries which are `boxed`, displayed with `display = "debug"`, given `fields`, or `no_bound`:
+///
+/// ```compile_fail
+/// use erro_rs::errors;
+///
+/// #[errors(backend = "thiserror", backtrace, std::io::Error)]
+/// fn foo() {
+///     Ok(())
+/// }
+/// ```
+///
+/// ```compile_fail
+/// use erro_rs::errors;
+///
+/// #[errors(backend = "anyhow", std::io::Error)]
+/// fn foo() {
+///     Ok(())
+/// }
+/// ```
+///
+/// ## `backtrace`
+///
+/// Each variant also stores a [`Backtrace`](std::backtrace::Backtrace), captured when it is
+/// created (e.g. by [`From`]), which can be retrieved with a `backtrace` method. Whether it is
+/// actually captured depends on the `RUST_BACKTRACE` and `RUST_LIB_BACKTRACE` environment
+/// variables, as explained by [`Backtrace::capture`](std::backtrace::Backtrace::capture). The
+/// backtrace is not included in the `Display` of the `Error`.
+///
+/// Since variants can no longer be constructed with only the `Error` they wrap, a constructor
+/// named after each variant in `snake_case` is generated too:
+///
+/// ```
+/// use std::{backtrace::BacktraceStatus, env};
+/// use erro_rs::errors;
+///
+/// #[errors(backtrace, std::io::Error, message = "Invalid")]
+/// fn read_int(path: &str) -> i128 {
+///     let content = std::fs::read_to_string(path)?;
+///     content.trim().parse().map_err(|_| ReadIntError::invalid(content))
+/// }
+///
+/// let err = read_int("/this/path/does/not/exist").unwrap_err();
+/// let enabled = env::var("RUST_LIB_BACKTRACE").or_else(|_| env::var("RUST_BACKTRACE")).is_ok_and(|v| v != "0");
+/// match err.backtrace().status() {
+///     BacktraceStatus::Captured => assert!(enabled),
+///     BacktraceStatus::Disabled => assert!(!enabled),
+///     _ => (),
+/// }
+///
+/// assert!(matches!(err, ReadIntError::StdIo(..)));
+/// assert!(!err.to_string().contains("backtrace"));
+/// ```
+///
+/// ## `capture`
+///
+/// Each variant also stores the [`Debug`](std::fmt::Debug) representation of the named arguments
+/// of the function, which is filled in whenever the function returns an `Error`. Each can be
+/// retrieved with a `captured_*` method:
+///
+/// ```
+/// use erro_rs::errors;
+///
+/// #[errors(capture(path, radix), std::io::Error, std::num::ParseIntError)]
+/// fn read_int(path: &str, radix: u32) -> i128 {
+///     let content = std::fs::read_to_string(path)?;
+///     Ok(i128::from_str_radix(content.trim(), radix)?)
+/// }
+///
+/// let err = read_int("/this/path/does/not/exist", 16).unwrap_err();
+/// assert!(matches!(err, ReadIntError::StdIo(..)));
+/// assert_eq!(err.captured_path(), "\"/this/path/does/not/exist\"");
+/// assert_eq!(err.captured_radix(), "16");
+/// ```
+///
+/// Only parameters of the function can be captured:
+///
+/// ```compile_fail
+/// use erro_rs::errors;
+///
+/// #[errors(capture(path), std::io::Error)]
+/// fn read(file: &str) -> String {
+///     Ok(std::fs::read_to_string(file)?)
+/// }
+/// ```
+///
+/// The arguments are only formatted once the function returns an `Error`, so they must not be
+/// moved by its body. Since the body is run by a closure to catch its `Error`s, functions which
+/// return an `impl Trait` cannot capture their arguments:
+///
+/// ```compile_fail
+/// use erro_rs::errors;
+///
+/// #[errors(capture(path), std::io::Error)]
+/// fn lines(path: &str) -> impl Iterator<Item = String> {
+///     Ok(std::fs::read_to_string(path)?.lines().map(String::from).collect::<Vec<_>>().into_iter())
+/// }
+/// ```
+///
+/// `Error`s which are created outside of the function (e.g. by [`From`]) capture empty strings.
+///
+/// ## `clone`
+///
+/// With `clone = "arc"`, each variant stores its `Error` in an [`Arc`](std::sync::Arc), so that the
+/// generated `Error` can derive [`Clone`] even if the `Error`s it wraps (e.g. [`std::io::Error`])
+/// cannot. The `Display` and `source` of each variant are unchanged, and [`From`] puts the `Error`
+/// into the `Arc`. An entry which is `Clone` already can be stored as it is with `no_arc`:
+///
+/// ```
+/// use std::{error::Error, sync::Arc};
+/// use erro_rs::errors;
+///
+/// #[errors(clone = "arc", helpers, std::io::Error, std::num::ParseIntError(no_arc))]
+/// fn read_int(path: &str) -> i128 {
+///     Ok(std::fs::read_to_string(path)?.trim().parse()?)
+/// }
+///
+/// let err = read_int("/this/path/does/not/exist").unwrap_err();
+/// let cloned = err.clone();
+/// assert_eq!(cloned.to_string(), err.to_string());
+/// assert!(cloned.source().unwrap().is::<std::io::Error>());
+///
+/// let io: Arc<std::io::Error> = cloned.into_std_io().unwrap();
+/// assert_eq!(io.kind(), std::io::ErrorKind::NotFound);
+///
+/// let parse = ReadIntError::from("a".parse::<i128>().unwrap_err());
+/// let _: Option<std::num::ParseIntError> = parse.clone().into_std_num_parse_int().ok();
+/// ```
+///
+/// As the `Error`s cannot be moved out of their `Arc`s, the `into_` methods of `helpers` return the
+/// `Arc` instead. `clone` cannot be combined with `backtrace` (since a
+/// [`Backtrace`](std::backtrace::Backtrace) is not `Clone`) or `into`.
+///
+/// ## `cold`
+///
+/// Marks each [`From`] implementation and constructor of the generated `Error` as `#[cold]` and
+/// `#[inline(never)]`, so that the compiler keeps them out of the paths which do not fail:
+///
+/// ```
+/// use erro_rs::errors;
+///
+/// #[errors(cold, helpers, std::num::ParseIntError)]
+/// fn sum(lines: &str) -> i128 {
+///     lines.lines().map(|l| Ok(l.trim().parse::<i128>()?)).sum()
+/// }
+///
+/// assert_eq!(sum("1\n2\n3").unwrap(), 6);
+/// assert!(sum("1\ntwo").unwrap_err().is_std_num_parse_int());
+/// ```
+///
+/// This is only worth it for functions which are called in hot loops and rarely fail, and should be
+/// measured; otherwise, the compiler is better left to decide.
+///
+/// ## `debug`
+///
+/// Prints the items which the macro generates (with the function it rewrote) to the standard error
+/// of the compiler, to help with seeing where an error comes from. Nothing about the expansion is
+/// changed:
+///
+/// ```
+/// use erro_rs::errors;
+///
+/// #[errors(debug, std::num::ParseIntError)]
+/// fn parse_int(s: &str) -> i128 {
+///     Ok(s.parse()?)
+/// }
+///
+/// assert!(matches!(parse_int("a"), Err(ParseIntError::StdNumParseInt(_))));
+/// ```
+///
+/// The output is only shown when the crate is compiled (e.g. by `cargo build -vv`, since `cargo`
+/// hides it otherwise for dependencies) and is not a stable format.
+///
+/// ## `declare_only`
+///
+/// Leaves the function as it is written, for those which would rather name the generated `Error` in
+/// their signature themselves. Only the `Error` (and the items which accompany it) is generated:
+///
+/// ```
+/// use erro_rs::errors;
+///
+/// #[errors(declare_only, std::io::Error, std::num::ParseIntError)]
+/// fn read_int(path: &str) -> Result<i128, ReadIntError> {
+///     Ok(std::fs::read_to_string(path)?.trim().parse()?)
+/// }
+///
+/// #[errors(declare_only, name = "IntError", std::num::ParseIntError)]
+/// fn parse_int(s: &str) -> Result<i128, IntError> {
+///     Ok(s.parse()?)
+/// }
+///
+/// assert!(matches!(read_int("/this/path/does/not/exist"), Err(ReadIntError::StdIo(_))));
+/// assert!(matches!(parse_int("a"), Err(IntError::StdNumParseInt(_))));
+/// ```
+///
+/// The `Error` which the function is written to return must be the one generated for it:
+///
+/// ```compile_fail
+/// use erro_rs::errors;
+///
+/// #[errors(declare_only, std::num::ParseIntError)]
+/// fn parse_int(s: &str) -> Result<i128, ParseIntErorr> {
+///     Ok(s.parse()?)
+/// }
+/// ```
+///
+/// It cannot be combined with `capture` (which rewrites the body) or `passthrough`.
+///
+/// ## `defer`
+///
+/// Holds the generated items back until [`emit_errors!`](macro@emit_errors) is called, so that
+/// where the `Error` is defined can be chosen (e.g. at the top of the file, or in a module of
+/// `Error`s). Only the function is rewritten where it is written:
+///
+/// ```
+/// use erro_rs::errors;
+///
+/// mod errors {
+///     erro_rs::emit_errors!(super::read_int);
+/// }
+///
+/// use errors::ReadIntError;
+///
+/// #[errors(defer, vis = "pub(super)", std::io::Error, std::num::ParseIntError)]
+/// fn read_int(path: &str) -> i128 {
+///     Ok(std::fs::read_to_string(path)?.trim().parse()?)
+/// }
+///
+/// fn main() {
+///     assert!(matches!(read_int("/this/path/does/not/exist"), Err(ReadIntError::StdIo(_))));
+/// }
+/// ```
+///
+/// Since the items are emitted elsewhere, the paths in the entries must resolve there too, and `vis`
+/// may be needed for the `Error` to be seen from the function. If the items are never emitted, the
+/// `Error` cannot be found:
+///
+/// ```compile_fail
+/// use erro_rs::errors;
+///
+/// #[errors(defer, std::num::ParseIntError)]
+/// fn parse_int(s: &str) -> i128 {
+///     Ok(s.parse()?)
+/// }
+/// ```
+///
+/// It can only be given to free functions.
+///
+/// ## `defmt`
+///
+/// With the [`defmt`](#defmt-2) feature, implements `defmt::Format` for the generated `Error`, which
+/// writes the name of each variant as an interned string alongside the `Error` it wraps. Most
+/// `Error`s only implement `Debug`, so they are written with `defmt::Debug2Format`, unless the
+/// entry is given the [`defmt`](#defmt-1) entry option:
+///
+#[cfg_attr(feature = "defmt", doc = "```")]
+#[cfg_attr(not(feature = "defmt"), doc = "```ignore")]
+/// #![no_std]
+///
+/// extern crate std as _;
+/// extern crate alloc;
+///
+/// use erro_rs::errors;
+///
+/// #[derive(Debug, defmt::Format)]
+/// struct SensorFault(u8);
+///
+/// impl core::fmt::Display for SensorFault {
+///     fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+///         write!(f, "sensor {} is faulty", self.0)
+///     }
+/// }
+///
+/// impl core::error::Error for SensorFault {}
+///
+/// #[errors(no_std, defmt, SensorFault(defmt), core::num::ParseIntError)]
+/// fn read_sensor(raw: &str) -> u8 {
+///     match raw.parse()? {
+///         0 => Err(SensorFault(0))?,
+///         n => Ok(n),
+///     }
+/// }
+///
+/// fn assert_format<T: defmt::Format>(_: &T) {}
+///
+/// fn main() {
+///     assert_format(&read_sensor("0").unwrap_err());
+/// }
+/// ```
+///
+/// The crate which uses the option must also depend on `defmt`.
+///
+/// ## `derive`
+///
+/// Derives additional traits for the generated `Error`, after `Debug`. Each `Error` it wraps must
+/// also implement those traits:
+///
+/// ```
+/// use erro_rs::errors;
+///
+/// #[errors(derive(Clone, PartialEq), std::num::ParseIntError)]
+/// fn parse(s: &str) -> i128 {
+///     Ok(s.parse()?)
+/// }
+///
+/// let err = parse("foo").unwrap_err();
+/// assert_eq!(err.clone(), err);
+/// assert_ne!(err, parse("").unwrap_err());
+/// ```
+///
+/// ## `diagnostic`
+///
+/// With the [`miette`](#miette) feature, implements `miette::Diagnostic` for the generated `Error`.
+/// Its `code` is the [`code`](#code) of each variant (if any), its `help` is given by the
+/// [`help`](#help) entry option, and its `diagnostic_source` is the `Error` of each entry given the
+/// [`diagnostic_source`](#diagnostic_source) option:
+///
+#[cfg_attr(feature = "miette", doc = "```")]
+#[cfg_attr(not(feature = "miette"), doc = "```ignore")]
+/// use erro_rs::errors;
+/// use miette::Diagnostic;
+///
+/// #[derive(Debug, Diagnostic)]
+/// #[diagnostic(code(config::invalid))]
+/// struct InvalidConfig;
+///
+/// impl std::fmt::Display for InvalidConfig {
+///     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+///         f.write_str("the config is invalid")
+///     }
+/// }
+///
+/// impl std::error::Error for InvalidConfig {}
+///
+/// #[errors(diagnostic, std::io::Error(code = 1, help = "check that the config exists"), InvalidConfig(diagnostic_source))]
+/// fn load(path: &str) -> String {
+///     let config = std::fs::read_to_string(path)?;
+///     match config.is_empty() {
+///         true => Err(InvalidConfig)?,
+///         false => Ok(config),
+///     }
+/// }
+///
+/// let err = load("/this/path/does/not/exist").unwrap_err();
+/// assert_eq!(err.code(), 1);
+/// assert_eq!(Diagnostic::code(&err).unwrap().to_string(), "1");
+/// assert_eq!(err.help().unwrap().to_string(), "check that the config exists");
+/// assert!(err.diagnostic_source().is_none());
+///
+/// let report = format!("{:?}", miette::Report::new(err));
+/// assert!(report.contains("check that the config exists"));
+///
+/// let err = LoadError::from(InvalidConfig);
+/// assert_eq!(Diagnostic::code(&err).unwrap().to_string(), "2");
+/// assert_eq!(err.diagnostic_source().unwrap().code().unwrap().to_string(), "config::invalid");
+/// ```
+///
+/// Since the `code` method of the generated `Error` returns the number itself, the `code` of
+/// `Diagnostic` must be called through the trait. The crate which uses the option must also depend
+/// on `miette`.
+///
+/// ## `display_code`
+///
+/// Includes the [`code`](#code) of each variant in the `Display` of the generated `Error`:
+///
+/// ```
+/// use erro_rs::errors;
+///
+/// #[errors(display_code, std::io::Error(code = 66), std::num::ParseIntError(code = 65))]
+/// fn read_int(path: &str) -> i128 {
+///     Ok(std::fs::read_to_string(path)?.parse()?)
+/// }
+///
+/// let err = read_int("/this/path/does/not/exist").unwrap_err();
+/// assert_eq!(err.to_string(), format!("[66] {}", std::error::Error::source(&err).unwrap()));
+/// ```
+///
+/// ## `display_prefix`
+///
+/// Prefixes the [`Display`](std::fmt::Display) of every variant with the name of the function, or
+/// with a custom string if one is given:
+///
+/// ```
+/// use erro_rs::errors;
+///
+/// #[errors(display_prefix, std::io::Error, std::num::ParseIntError)]
+/// fn read_int(path: &str) -> i128 {
+///     Ok(std::fs::read_to_string(path)?.parse()?)
+/// }
+///
+/// #[errors(display_prefix = "while reading the configured port", std::num::ParseIntError)]
+/// fn read_port(s: &str) -> u16 {
+///     Ok(s.parse()?)
+/// }
+///
+/// let io = ReadIntError::from(std::io::Error::new(std::io::ErrorKind::NotFound, "not found"));
+/// let parse = ReadIntError::from("x".parse::<i128>().unwrap_err());
+///
+/// assert_eq!(io.to_string(), "read_int: not found");
+/// assert_eq!(parse.to_string(), "read_int: invalid digit found in string");
+/// assert_eq!(read_port("x").unwrap_err().to_string(), "while reading the configured port: invalid digit found in string");
+/// ```
+///
+/// ## `doc`
+///
+/// Replaces the documentation of the generated `Error`. Each line of the string becomes its own
+/// `#[doc]` attribute, so paragraphs render as they would in a doc comment:
+///
+/// ```
+/// use erro_rs::errors;
+///
+/// /// Load the configuration file.
+/// #[errors(doc = "Errors produced while loading the configuration file.
+///
+/// Every variant wraps the underlying cause.", std::io::Error)]
+/// pub fn load_config() {
+///     Ok(())
+/// }
+///
+/// assert!(load_config().is_ok());
+/// ```
+///
+/// ## `dyn_impls`
+///
+/// Implements [`AsRef<dyn Error>`](AsRef) for the generated `Error`, and asserts at its definition
+/// that it converts into a `Box<dyn Error + Send + Sync>` (through the implementation of [`From`]
+/// which the standard library provides for every such `Error`), for use with functions which only
+/// know the `Error` as a trait object:
+///
+/// ```
+/// use std::error::Error;
+/// use erro_rs::errors;
+///
+/// #[errors(dyn_impls, std::num::ParseIntError)]
+/// fn parse(s: &str) -> i128 {
+///     Ok(s.parse()?)
+/// }
+///
+/// fn describe(err: &dyn Error) -> String {
+///     format!("failed: {}", err)
+/// }
+///
+/// fn report<E: Into<Box<dyn Error + Send + Sync>>>(err: E) -> String {
+///     describe(&*err.into())
+/// }
+///
+/// let err = parse("foo").unwrap_err();
+/// assert_eq!(describe(err.as_ref()), "failed: invalid digit found in string");
+/// assert_eq!(report(err), "failed: invalid digit found in string");
+/// ```
+///
+/// ```compile_fail
+/// use erro_rs::errors;
+///
+/// #[derive(Debug)]
+/// struct LocalError(std::rc::Rc<()>);
+///
+/// impl std::fmt::Display for LocalError {
+///     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+///         f.write_str("local")
+///     }
+/// }
+///
+/// impl std::error::Error for LocalError {}
+///
+/// // `LocalError` is not `Send`, so the `Error` cannot be boxed
+/// #[errors(dyn_impls, LocalError)]
+/// fn foo() {
+///     Ok(())
+/// }
+/// ```
+///
+/// It cannot be combined with [`no_std`](#no_std).
+///
+/// ## `enum_attrs`
+///
+/// Places attributes on the generated `Error`. They are emitted in the order they are written,
+/// after the generated `#[doc]` and `#[derive]` (so that derive helper attributes, such as
+/// `#[serde(...)]`, are in scope):
+///
+/// ```
+/// use erro_rs::errors;
+///
+/// #[errors(enum_attrs(#[derive(Clone)], #[cfg_attr(all(), derive(PartialEq))]), std::num::ParseIntError)]
+/// fn parse(s: &str) -> i128 {
+///     Ok(s.parse()?)
+/// }
+///
+/// let err = parse("foo").unwrap_err();
+/// assert_eq!(err.clone(), err);
+/// ```
+///
+/// ```compile_fail
+/// use erro_rs::errors;
+///
+/// #[errors(enum_attrs(doc(hidden)), std::io::Error)]
+/// fn foo() {
+///     Ok(())
+/// }
+/// ```
+///
+/// ## `eq_kind`
+///
+/// Generates the [`kind`](#kind) of the `Error`, and compares the two by variant alone, so that an
+/// `Error` can be compared in tests even when what it wraps (e.g. [`std::io::Error`]) does not
+/// implement [`PartialEq`]. The `eq_kind` method does the same for two `Error`s:
+///
+/// ```
+/// use erro_rs::errors;
+///
+/// #[errors(eq_kind, std::io::Error, std::num::ParseIntError)]
+/// fn read_int(path: &str) -> i128 {
+///     Ok(std::fs::read_to_string(path)?.parse()?)
+/// }
+///
+/// let err = read_int("/this/path/does/not/exist").unwrap_err();
+///
+/// assert_eq!(err, ReadIntErrorKind::StdIo);
+/// assert_ne!(ReadIntErrorKind::StdNumParseInt, err);
+/// assert!(err.eq_kind(&read_int("/neither/does/this/one").unwrap_err()));
+/// ```
+///
+/// To compare what each variant wraps as well, use [`derive(PartialEq)`](#derive) instead, which
+/// requires every `Error` to implement [`PartialEq`].
+///
+/// ## `errors_doc`
+///
+/// Appends an "Errors" section to the documentation of the function, which lists each variant of
+/// the generated `Error` and the `Error` it wraps. This satisfies [`clippy::missing_errors_doc`].
+///
+/// ```
+/// use erro_rs::errors;
+///
+/// /// Read an integer from a file.
+/// #[errors(errors_doc, std::io::Error, std::num::ParseIntError)]
+/// pub fn read_int(path: &str) -> i128 {
+///     Ok(std::fs::read_to_string(path)?.parse()?)
+/// }
+///
+/// assert!(read_int("/this/path/does/not/exist").is_err());
+/// ```
+///
+/// [`clippy::missing_errors_doc`]: https://rust-lang.github.io/rust-clippy/master/index.html#missing_errors_doc
+///
+/// ## `flatten`
+///
+/// With `flatten(...)`, the variants of another generated `Error` are declared again in this one,
+/// rather than wrapping it whole, and [`From`] converts each of its variants into the variant of
+/// the same name. Since the macro cannot see the other `Error`, its variants must be restated, as in
+/// `flatten(ReadIntError {StdIo: std::io::Error, ...})`. Several `Error`s can be flattened at once:
+///
+/// ```
+/// use std::convert::TryFrom;
+/// use erro_rs::errors;
+///
+/// #[errors(std::io::Error, std::num::ParseIntError)]
+/// fn read_int(path: &str) -> i128 {
+///     Ok(std::fs::read_to_string(path)?.parse()?)
+/// }
+///
+/// #[errors(
+///     flatten(ReadIntError {StdIo: std::io::Error, StdNumParseInt: std::num::ParseIntError}),
+///     std::num::TryFromIntError,
+/// )]
+/// fn read_port(path: &str) -> u16 {
+///     Ok(u16::try_from(read_int(path)?)?)
+/// }
+///
+/// fn main() {
+///     assert!(matches!(read_port("/this/path/does/not/exist"), Err(ReadPortError::StdIo(_))));
+/// }
+/// ```
+///
+/// Flattened variants must not have the same name as any other:
+///
+/// ```compile_fail
+/// use erro_rs::errors;
+///
+/// #[errors(std::io::Error)]
+/// fn read() -> String {
+///     Ok(std::fs::read_to_string("foo")?)
+/// }
+///
+/// #[errors(flatten(ReadError {StdIo: std::io::Error}), std::fmt::Error = "StdIo")]
+/// fn foo() {
+///     Ok(())
+/// }
+/// ```
+///
+/// ## `helpers`
+///
+/// Generates `is_*`, `as_*`, and `into_*` methods for each variant of the generated `Error`, named
+/// after the variant (or its alias) in `snake_case`:
+///
+/// ```
+/// use std::io;
+/// use erro_rs::errors;
+///
+/// #[errors(helpers, std::io::Error, std::num::ParseIntError = "Type")]
+/// fn read_int(path: &str) -> i128 {
+///     Ok(std::fs::read_to_string(path)?.parse()?)
+/// }
+///
+/// let err = read_int("/this/path/does/not/exist").unwrap_err();
+/// assert!(err.is_std_io());
+/// assert!(!err.is_type());
+///
+/// assert_eq!(err.as_std_io().map(io::Error::kind), Some(io::ErrorKind::NotFound));
+/// assert!(err.as_type().is_none());
+///
+/// let err = err.into_type().unwrap_err();
+/// let io_err = err.into_std_io().unwrap();
+/// assert_eq!(io_err.kind(), io::ErrorKind::NotFound);
+///
+/// let err = ReadIntError::from(io_err);
+/// assert!(err.into_std_io().is_ok());
+/// ```
+///
+/// Variants whose methods would have the same name are rejected:
+///
+/// ```compile_fail
+/// use erro_rs::errors;
+///
+/// #[errors(helpers, std::io::Error = "IOError", std::fmt::Error = "IoError")]
+/// fn foo() {
+///     Ok(())
+/// }
+/// ```
+///
+/// ## `infer`
+///
+/// Adds the `Error`s propagated by the `?`s in the body of the function which can be recognized, so
+/// that they need not be listed. A proc macro cannot know the type of an expression, so this is a
+/// best-effort heuristic which recognizes a `?` applied to:
+///
+/// - a call to a well-known function of `std` by the last two segments of its path, e.g.
+///   `fs::read_to_string(...)` or `std::env::var(...)`;
+/// - a call to one of the `io` methods `flush`, `read_exact`, `read_line`, `read_to_end`,
+///   `read_to_string`, `sync_all`, or `write_all`, or to `duration_since`;
+/// - `parse::<T>()`, where `T` is a primitive number, `bool`, `char`, or an address of
+///   `std::net`;
+/// - a call to a function declared in the body which returns a `Result<T, E>`.
+///
+/// The `?`s within closures and nested functions are ignored, since they do not return from the
+/// function (nor do those in `async` blocks, unless `wrap = "future_output"` is given):
+///
+/// ```
+/// use std::fs;
+/// use erro_rs::errors;
+///
+/// #[errors(infer)]
+/// fn read_int(path: &str) -> i128 {
+///     let content = fs::read_to_string(path)?;
+///     Ok(content.trim().parse::<i128>()?)
+/// }
+///
+/// assert!(matches!(read_int("/this/path/does/not/exist"), Err(ReadIntError::StdIo(_))));
+/// assert!(matches!(ReadIntError::from("a".parse::<i128>().unwrap_err()), ReadIntError::StdNumParseInt(_)));
+/// ```
+///
+/// Each `?` which cannot be recognized is reported, unless any `Error`s are listed alongside `infer`,
+/// in which case they are assumed to cover it:
+///
+/// ```
+/// use erro_rs::errors;
+///
+/// #[errors(infer, std::num::ParseIntError)]
+/// fn read_int(path: &str) -> i128 {
+///     let content = std::fs::read_to_string(path)?;
+///     Ok(content.trim().parse()?)
+/// }
+///
+/// assert!(matches!(read_int("/this/path/does/not/exist"), Err(ReadIntError::StdIo(_))));
+/// ```
+///
+/// ```compile_fail
+/// use erro_rs::errors;
+///
+/// #[errors(infer)]
+/// fn read_int(path: &str) -> i128 {
+///     let content = std::fs::read_to_string(path)?;
+///     Ok(content.trim().parse()?)
+/// }
+/// ```
+///
+/// `infer` cannot be given to an `impl` block or module.
+///
+/// ## `into`
+///
+/// With `into = ...`, the generated `Error` can be converted into the given `Error` (e.g. one for
+/// the whole application) by converting the `Error` wrapped by each variant into it. Then `?` can
+/// be used on the function from others which return that `Error`. The option can be given more
+/// than once, to convert into several `Error`s:
+///
+/// ```
+/// use std::{io, num::ParseIntError};
+/// use erro_rs::errors;
+///
+/// #[derive(Debug)]
+/// enum AppError {
+///     Io(io::Error),
+///     Parse(ParseIntError),
+/// }
+///
+/// impl From<io::Error> for AppError {
+///     fn from(e: io::Error) -> Self {
+///         Self::Io(e)
+///     }
+/// }
+///
+/// impl From<ParseIntError> for AppError {
+///     fn from(e: ParseIntError) -> Self {
+///         Self::Parse(e)
+///     }
+/// }
+///
+/// #[errors(into = AppError, io::Error, ParseIntError(boxed))]
+/// fn read_int(path: &str) -> i128 {
+///     Ok(std::fs::read_to_string(path)?.parse()?)
+/// }
+///
+/// fn run() -> Result<i128, AppError> {
+///     Ok(read_int("/this/path/does/not/exist")?)
+/// }
+///
+/// fn main() {
+///     assert!(matches!(run(), Err(AppError::Io(_))));
+/// }
+/// ```
+///
+/// ## `into_io`
+///
+/// Implements [`From`] for [`std::io::Error`], converting the generated `Error` into one for use
+/// within traits which require it (e.g. [`Read`](std::io::Read)). The generated `Error` becomes the
+/// source of an [`Other`](std::io::ErrorKind::Other) error, so it can be downcast back:
+///
+/// ```
+/// use erro_rs::errors;
+///
+/// #[errors(into_io, std::num::ParseIntError)]
+/// fn parse(s: &str) -> i128 {
+///     Ok(s.parse()?)
+/// }
+///
+/// fn parse_io(s: &str) -> std::io::Result<i128> {
+///     Ok(parse(s)?)
+/// }
+///
+/// let err = parse_io("foo").unwrap_err();
+/// assert_eq!(err.kind(), std::io::ErrorKind::Other);
+/// assert!(matches!(err.into_inner().unwrap().downcast::<ParseError>().as_deref(), Ok(ParseError::StdNumParseInt(_))));
+/// ```
+///
+/// The generated `Error` must be [`Send`] and [`Sync`], and the option cannot be combined with
+/// [`no_std`](#no_std).
+///
+/// ## `into_response`
+///
+/// With the [`axum`](#axum) feature, implements `axum::response::IntoResponse` for the generated
+/// `Error`, so that handlers can return it directly. The response has the
+/// [`status_code`](#status) of the variant (which is generated alongside it), and the `Display` of
+/// the `Error` as its body:
+///
+#[cfg_attr(feature = "axum", doc = "```")]
+#[cfg_attr(not(feature = "axum"), doc = "```ignore")]
+/// use axum::{body::Body, http::{Request, StatusCode}, routing::get, Router};
+/// use tower::ServiceExt;
+/// use erro_rs::errors;
+///
+/// #[errors(into_response, std::io::Error, std::num::ParseIntError(status = 400))]
+/// fn read_int(path: &str) -> i128 {
+///     Ok(std::fs::read_to_string(path)?.trim().parse()?)
+/// }
+///
+/// async fn missing() -> Result<String, ReadIntError> {
+///     Ok(read_int("/this/path/does/not/exist")?.to_string())
+/// }
+///
+/// async fn invalid() -> Result<String, ReadIntError> {
+///     let path = std::env::temp_dir().join("erro-rs-into-response");
+///     std::fs::write(&path, "foo").unwrap();
+///     Ok(read_int(path.to_str().unwrap())?.to_string())
+/// }
+///
+/// async fn send(app: &Router, uri: &str) -> (StatusCode, String) {
+///     let response = app.clone().oneshot(Request::get(uri).body(Body::empty()).unwrap()).await.unwrap();
+///     let status = response.status();
+///     let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+///     (status, String::from_utf8(body.to_vec()).unwrap())
+/// }
+///
+/// #[tokio::main(flavor = "current_thread")]
+/// async fn main() {
+///     let app = Router::new().route("/missing", get(missing)).route("/invalid", get(invalid));
+///
+///     assert_eq!(send(&app, "/missing").await, (
+///         StatusCode::INTERNAL_SERVER_ERROR,
+///         "No such file or directory (os error 2)".into(),
+///     ));
+///
+///     assert_eq!(send(&app, "/invalid").await, (StatusCode::BAD_REQUEST, "invalid digit found in string".into()));
+/// }
+/// ```
+///
+/// With the [`serialize`](#serialize) option too, the body is JSON instead, with the `Display` of
+/// the `Error` as its `error` and the name of the variant as its `kind`:
+///
+#[cfg_attr(all(feature = "axum", feature = "serde"), doc = "```")]
+#[cfg_attr(not(all(feature = "axum", feature = "serde")), doc = "```ignore")]
+/// use axum::{body::Body, http::{Request, StatusCode}, routing::get, Router};
+/// use tower::ServiceExt;
+/// use erro_rs::errors;
+///
+/// #[errors(into_response, serialize, std::io::Error, std::num::ParseIntError(status = 400))]
+/// fn read_int(path: &str) -> i128 {
+///     Ok(std::fs::read_to_string(path)?.trim().parse()?)
+/// }
+///
+/// async fn missing() -> Result<String, ReadIntError> {
+///     Ok(read_int("/this/path/does/not/exist")?.to_string())
+/// }
+///
+/// async fn invalid() -> Result<String, ReadIntError> {
+///     let path = std::env::temp_dir().join("erro-rs-into-response-json");
+///     std::fs::write(&path, "foo").unwrap();
+///     Ok(read_int(path.to_str().unwrap())?.to_string())
+/// }
+///
+/// async fn send(app: &Router, uri: &str) -> (StatusCode, serde_json::Value) {
+///     let response = app.clone().oneshot(Request::get(uri).body(Body::empty()).unwrap()).await.unwrap();
+///     let status = response.status();
+///     let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+///     (status, serde_json::from_slice(&body).unwrap())
+/// }
+///
+/// #[tokio::main(flavor = "current_thread")]
+/// async fn main() {
+///     let app = Router::new().route("/missing", get(missing)).route("/invalid", get(invalid));
+///
+///     assert_eq!(send(&app, "/missing").await, (StatusCode::INTERNAL_SERVER_ERROR, serde_json::json!({
+///         "error": "No such file or directory (os error 2)",
+///         "kind": "StdIo",
+///     })));
+///
+///     assert_eq!(send(&app, "/invalid").await, (StatusCode::BAD_REQUEST, serde_json::json!({
+///         "error": "invalid digit found in string",
+///         "kind": "StdNumParseInt",
+///     })));
+/// }
+/// ```
+///
+/// The crate which uses the option must also depend on `axum`, and the option cannot be combined
+/// with [`no_std`](#no_std).
+///
+/// ## `keep_suffix`
+///
+/// Keeps the trailing "Error" of each path when deriving variant names:
+///
+/// ```
+/// use std::fmt;
+/// use erro_rs::errors;
+///
+/// mod openssl {
+///     pub mod error {
+///         #[derive(Debug)]
+///         pub struct ErrorStack;
+///
+///         impl std::fmt::Display for ErrorStack {
+///             fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+///                 write!(f, "error stack")
+///             }
+///         }
+///
+///         impl std::error::Error for ErrorStack {}
+///     }
+/// }
+///
+/// #[errors(keep_suffix, openssl::error::ErrorStack, std::io::Error)]
+/// fn handshake() {
+///     Ok(())
+/// }
+///
+/// #[errors(openssl::error::ErrorStack, std::io::Error)]
+/// fn connect() {
+///     Ok(())
+/// }
+///
+/// fn main() {
+///     assert!(matches!(handshake(), Ok(()) | Err(HandshakeError::OpensslErrorErrorStack(_) | HandshakeError::StdIoError(_))));
+///     assert!(matches!(connect(), Ok(()) | Err(ConnectError::OpensslErrorErrorStack(_) | ConnectError::StdIo(_))));
+/// }
+/// ```
+///
+/// ## `kind`
+///
+/// Generates a fieldless `enum` with the same variants (and visibility) as the `Error`, and a
+/// `kind` method which returns the variant of the `Error` without the `Error` it wraps:
+///
+/// ```
+/// use erro_rs::errors;
+///
+/// #[errors(kind, std::io::Error, std::num::ParseIntError)]
+/// fn read_int(path: &str) -> i128 {
+///     Ok(std::fs::read_to_string(path)?.parse()?)
+/// }
+///
+/// let kind = read_int("/this/path/does/not/exist").unwrap_err().kind();
+/// let copy = kind;
+///
+/// assert_eq!(kind, ReadIntErrorKind::StdIo);
+/// assert_eq!(copy, kind);
+/// assert_ne!(kind, ReadIntErrorKind::StdNumParseInt);
+/// ```
+///
+/// ## `location`
+///
+/// Each variant also stores the [`Location`](std::panic::Location) where it was created, which can
+/// be retrieved with a `location` method, and is appended to the alternate `Display` (`{:#}`). For
+/// `Error`s converted by `?`, this is the location of the `?`, so closures which use `?` on their
+/// own `Result`s are unaffected.
+///
+/// Like [`backtrace`](#backtrace), a constructor is generated for each variant, which records the
+/// location of its caller:
+///
+/// ```
+/// use erro_rs::errors;
+///
+/// #[errors(location, std::num::ParseIntError, message)]
+/// fn sum(items: &[&str]) -> i32 {
+///     let parse = |s: &str| -> Result<i32, std::num::ParseIntError> { Ok(s.trim().parse()?) };
+///     let first = parse(items[0])?;
+///     let second = parse(items.get(1).ok_or_else(|| SumError::message("too short".into()))?)?;
+///     Ok(first + second)
+/// }
+///
+/// let line = line!();
+/// let first = sum(&["a", "1"]).unwrap_err();
+/// let second = sum(&["1", "a"]).unwrap_err();
+/// let short = sum(&["1"]).unwrap_err();
+///
+/// assert_eq!(first.location().file(), file!());
+/// assert_eq!(first.location().line(), line - 5);
+/// assert_eq!(second.location().line(), line - 4);
+/// assert_eq!(short.location().line(), line - 4);
+///
+/// assert!(format!("{:#}", first).ends_with(&format!(" (at {})", first.location())));
+/// assert!(!first.to_string().contains(file!()));
+/// ```
+///
+/// ## `log`
+///
+/// With the [`log`](#log-1) feature, `log = "warn"` (or `"error"`, `"info"`, `"debug"`, or
+/// `"trace"`) makes each [`From`] implementation of the generated `Error` log the `Error` it
+/// converts, with the `erro_rs` target, before wrapping it. The level of an entry can be changed
+/// with the [`level`](#level) entry option:
+///
+#[cfg_attr(feature = "log", doc = "```")]
+#[cfg_attr(not(feature = "log"), doc = "```ignore")]
+/// use std::sync::Mutex;
+/// use erro_rs::errors;
+///
+/// static EVENTS: Mutex<Vec<String>> = Mutex::new(Vec::new());
+///
+/// struct Logger;
+///
+/// impl log::Log for Logger {
+///     fn enabled(&self, _: &log::Metadata) -> bool {
+///         true
+///     }
+///
+///     fn log(&self, record: &log::Record) {
+///         let event = format!("{} {}: {}", record.level(), record.target(), record.args());
+///         EVENTS.lock().unwrap().push(event);
+///     }
+///
+///     fn flush(&self) {}
+/// }
+///
+/// #[errors(log = "warn", std::io::Error(level = "error"), std::num::ParseIntError)]
+/// fn read_int(path: &str) -> i128 {
+///     Ok(std::fs::read_to_string(path)?.trim().parse()?)
+/// }
+///
+/// log::set_logger(&Logger).unwrap();
+/// log::set_max_level(log::LevelFilter::Trace);
+///
+/// let path = std::env::temp_dir().join("erro-rs-log");
+/// std::fs::write(&path, "foo").unwrap();
+/// assert!(read_int("/this/path/does/not/exist").is_err());
+/// assert!(read_int(path.to_str().unwrap()).is_err());
+///
+/// assert_eq!(*EVENTS.lock().unwrap(), [
+///     "ERROR erro_rs: read_int: No such file or directory (os error 2)",
+///     "WARN erro_rs: read_int: invalid digit found in string",
+/// ]);
+/// ```
+///
+/// The crate which uses the option must also depend on `log`. Without the option, the conversions
+/// do not log anything.
+///
+/// ## `max_size`
+///
+/// Asserts that the generated `Error` takes up no more than the given number of bytes, since every
+/// `Result` which returns it is at least as large. An `Error` which is too large is reported at the
+/// attribute, and its largest entries can be given `boxed` to fit:
+///
+/// ```
+/// use erro_rs::errors;
+///
+/// #[errors(max_size = 32, std::io::Error, "[u8; 1024]"(alias = "Huge", boxed, display = "debug"))]
+/// fn read(path: &str) -> Vec<u8> {
+///     Ok(std::fs::read(path)?)
+/// }
+///
+/// assert!(matches!(read("/this/path/does/not/exist"), Err(ReadError::StdIo(_))));
+/// ```
+///
+/// ```compile_fail
+/// use erro_rs::errors;
+///
+/// #[errors(max_size = 32, std::io::Error, "[u8; 1024]"(alias = "Huge", display = "debug"))]
+/// fn read(path: &str) -> Vec<u8> {
+///     Ok(std::fs::read(path)?)
+/// }
+/// ```
+///
+/// The size is that of the configuration being compiled, so it does not count variants whose `cfg`
+/// is not enabled. `Error`s which refer to the generic types or constants of the function cannot
+/// be checked, but those which only borrow with its lifetimes can:
+///
+/// ```
+/// use std::{error::Error, fmt};
+/// use erro_rs::errors;
+///
+/// #[derive(Debug)]
+/// struct Borrowed<'a>(&'a str);
+///
+/// impl fmt::Display for Borrowed<'_> {
+///     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+///         write!(f, "cannot parse `{}`", self.0)
+///     }
+/// }
+///
+/// impl Error for Borrowed<'_> {}
+///
+/// #[errors(max_size = 64, "Borrowed<'a>", std::num::ParseIntError)]
+/// fn parse<'a>(s: &'a str) -> u8 {
+///     if s.is_empty() {
+///         return Err(Borrowed(s).into());
+///     }
+///
+///     Ok(s.parse()?)
+/// }
+///
+/// assert!(matches!(parse(""), Err(ParseError::Borrowed(_))));
+/// ```
+///
+/// ## `message`
+///
+/// Adds a `Message` variant (or another name, with `message = "..."`) which wraps a [`String`]. Its
+/// `Display` writes the message as-is, and it has no `source`. It can be given more than once, as
+/// long as each message has a different name:
+///
+/// ```
+/// use std::error::Error;
+/// use erro_rs::errors;
+///
+/// #[errors(message = "Invalid", std::num::ParseIntError)]
+/// fn parse(s: &str) -> u8 {
+///     if s.is_empty() {
+///         Err("nothing to parse")?;
+///     }
+///
+///     Ok(s.parse()?)
+/// }
+///
+/// #[errors(message = "TooLow", message = "TooHigh")]
+/// fn check(n: u8) {
+///     match n {
+///         0..=9 => Err(CheckError::TooLow(format!("{} is too low", n))),
+///         10..=99 => Ok(()),
+///         _ => Err(CheckError::TooHigh(format!("{} is too high", n))),
+///     }
+/// }
+///
+/// let err = parse("").unwrap_err();
+/// assert!(matches!(err, ParseError::Invalid(_)));
+/// assert_eq!(err.to_string(), "nothing to parse");
+/// assert!(err.source().is_none());
+///
+/// assert!(matches!(ParseError::from(String::from("no")), ParseError::Invalid(_)));
+/// assert_eq!(check(100).unwrap_err().to_string(), "100 is too high");
+/// ```
+///
+/// When there is only one message, [`From`] is implemented for both [`String`] and [`&str`](str),
+/// so that `?` can be used on either. Otherwise, each message must be constructed by name.
+///
+/// ## `module`
+///
+/// Declares the generated items in a module of their own (named after the `Error`, e.g.
+/// `read_int_error`, unless another name is given), and re-exports them beside the function. Items
+/// in the module are made as visible as the function, whether it is public or private:
+///
+/// ```
+/// mod files {
+///     use erro_rs::errors;
+///
+///     #[errors(module, result_alias, std::io::Error, std::num::ParseIntError)]
+///     pub fn read_int(path: &str) -> i128 {
+///         Ok(std::fs::read_to_string(path)?.trim().parse()?)
+///     }
+///
+///     #[errors(module = "errors", kind, std::num::ParseIntError)]
+///     fn parse_int(s: &str) -> i128 {
+///         Ok(s.parse()?)
+///     }
+///
+///     pub fn parses(s: &str) -> bool {
+///         let result: Result<_, ParseIntError> = parse_int(s);
+///         !matches!(result.map_err(|e| e.kind()), Err(ParseIntErrorKind::StdNumParseInt))
+///     }
+/// }
+///
+/// let result: files::ReadIntResult<_> = files::read_int("/this/path/does/not/exist");
+/// assert!(matches!(result, Err(files::ReadIntError::StdIo(_))));
+/// assert!(!files::parses("a"));
+/// ```
+///
+/// It cannot be combined with `share`, since the functions which share an `Error` may not be in the
+/// same module.
+///
+/// ## `must_use`
+///
+/// Marks the generated `Error` as `#[must_use]` (with a message, if given with
+/// `must_use = "..."`), so that an `Error` which is constructed (e.g. by a helper function) and then
+/// discarded is warned about:
+///
+/// ```
+/// use erro_rs::errors;
+///
+/// #[errors(must_use = "this error should be handled or propagated", derive(PartialEq), std::num::ParseIntError)]
+/// fn parse(s: &str) -> u8 {
+///     Ok(s.parse()?)
+/// }
+///
+/// fn invalid() -> ParseError {
+///     ParseError::from("a".parse::<u8>().unwrap_err())
+/// }
+///
+/// assert_eq!(parse("a").unwrap_err(), invalid());
+/// ```
+///
+/// ```compile_fail
+/// #![deny(unused_must_use)]
+/// use erro_rs::errors;
+///
+/// #[errors(must_use, std::num::ParseIntError)]
+/// fn parse(s: &str) -> u8 {
+///     Ok(s.parse()?)
+/// }
+///
+/// fn main() {
+///     ParseError::from("a".parse::<u8>().unwrap_err());
+/// }
+/// ```
+///
+/// ## `name`
+///
+/// Overrides the name of the generated `Error`, which is otherwise derived from the function's (or
+/// that of its `impl` block, module, or trait):
+///
+/// ```
+/// use erro_rs::errors;
+///
+/// #[errors(name = "ConfigError", std::io::Error)]
+/// fn load_config(path: &str) -> String {
+///     Ok(std::fs::read_to_string(path)?)
+/// }
+///
+/// assert!(matches!(load_config("/this/path/does/not/exist"), Err(ConfigError::StdIo(_))));
+/// ```
+///
+/// It cannot be combined with `share`, which names the `Error` already.
+///
+/// ## `naming`
+///
+/// By default, every segment of the path to an `Error` is used to name its variant. With
+/// `naming = "last"`, only the last segment which contributes to the name is used instead:
+///
+/// ```
+/// use erro_rs::errors;
+///
+/// #[errors(naming = "last", std::io::Error, std::sync::mpsc::RecvError)]
+/// fn recv_file(rx: &std::sync::mpsc::Receiver<String>) -> String {
+///     let path = rx.recv()?;
+///     Ok(std::fs::read_to_string(path)?)
+/// }
+///
+/// let (tx, rx) = std::sync::mpsc::channel::<String>();
+/// drop(tx);
+///
+/// assert!(matches!(recv_file(&rx), Err(RecvFileError::Recv(_))));
+/// assert!(matches!(RecvFileError::from(std::io::Error::from_raw_os_error(2)), RecvFileError::Io(_)));
+/// ```
+///
+/// Entries which share a last segment must then be given aliases:
+///
+/// ```compile_fail
+/// use erro_rs::errors;
+///
+/// mod fmt {
+///     #[derive(Debug)]
+///     pub struct Error;
+///
+///     impl std::fmt::Display for Error {
+///         fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+///             f.write_str("error")
+///         }
+///     }
+///
+///     impl std::error::Error for Error {}
+/// }
+///
+/// // Both are named `Fmt`
+/// #[errors(naming = "last", std::fmt::Error, crate::fmt::Error)]
+/// fn foo() {
+///     Ok(())
+/// }
+///
+/// fn main() {}
+/// ```
+///
+/// ## `nested`
+///
+/// When `Error`s are given to a module (see the remarks), this rewrites the functions of its nested
+/// modules to return its `Error` too, which each nested module imports:
+///
+/// ```
+/// use erro_rs::errors;
+///
+/// #[errors(nested, result_alias, std::num::ParseIntError)]
+/// mod config {
+///     pub mod env {
+///         pub fn port(value: &str) -> u16 {
+///             Ok(value.parse()?)
+///         }
+///
+///         pub fn ports(values: &[&str]) -> ConfigResult<Vec<u16>> {
+///             values.iter().map(|v| port(v)).collect()
+///         }
+///     }
+/// }
+///
+/// assert!(matches!(config::env::port("eighty"), Err(config::ConfigError::StdNumParseInt(_))));
+/// assert_eq!(config::env::ports(&["80", "443"]).unwrap(), [80, 443]);
+/// ```
+///
+/// ## `no_display`
+///
+/// Leaves the [`Display`](std::fmt::Display) of the generated `Error` to be written by hand, for
+/// messages which should be shown to users. Everything else (e.g. [`Debug`], [`From`], and
+/// [`Error`](std::error::Error)) is generated as usual:
+///
+/// ```
+/// use std::{error::Error, fmt};
+/// use erro_rs::errors;
+///
+/// #[errors(no_display, std::io::Error, std::num::ParseIntError)]
+/// fn read_int(path: &str) -> i128 {
+///     Ok(std::fs::read_to_string(path)?.trim().parse()?)
+/// }
+///
+/// impl fmt::Display for ReadIntError {
+///     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+///         match self {
+///             Self::StdIo(_) => f.write_str("the number could not be read"),
+///             Self::StdNumParseInt(_) => f.write_str("the file does not contain a number"),
+///         }
+///     }
+/// }
+///
+/// let err = read_int("/this/path/does/not/exist").unwrap_err();
+/// assert_eq!(err.to_string(), "the number could not be read");
+/// assert!(err.source().unwrap().is::<std::io::Error>());
+/// ```
+///
+/// It cannot be combined with the options which only change the generated `Display` (e.g.
+/// `display_prefix`, `display_code`, or `display` on an entry):
+///
+/// ```compile_fail
+/// use erro_rs::errors;
+///
+/// #[errors(no_display, display_prefix, std::num::ParseIntError)]
+/// fn parse_int(s: &str) -> i128 {
+///     Ok(s.parse()?)
+/// }
+/// ```
+///
+/// ## `no_std`
+///
+/// The generated items refer to `core` (and to `alloc`, for a [`Box`] or `String`) rather than
+/// `std`, so that they can be used by a `#![no_std]` crate which declares `extern crate alloc`:
+///
+/// ```
+/// #![no_std]
+///
+/// // Links `std` for the test harness, without letting the expansion refer to it.
+/// extern crate std as _;
+/// extern crate alloc;
+///
+/// use erro_rs::errors;
+///
+/// #[errors(no_std, kind, message, core::num::ParseIntError, core::str::Utf8Error(boxed))]
+/// fn parse(bytes: &[u8]) -> i128 {
+///     Ok(core::str::from_utf8(bytes)?.parse()?)
+/// }
+///
+/// fn main() {
+///     assert_eq!(parse(b"12").unwrap(), 12);
+///     assert_eq!(parse(b"foo").unwrap_err().kind(), ParseErrorKind::CoreNumParseInt);
+///     assert!(matches!(parse(&[0xff]), Err(ParseError::CoreStrUtf8(_))));
+///     assert!(matches!(ParseError::from("foo"), ParseError::Message(_)));
+/// }
+/// ```
+///
+/// Since they require `std`, it cannot be combined with `backtrace` or `exit_code`, nor with an
+/// `Error` (or field) whose type refers to `std` (e.g. [`std::io::Error`]). The `other` option is
+/// rejected too, since its `Error` is written through `std`; list
+/// `"alloc::boxed::Box<dyn core::error::Error + Send + Sync>"` instead:
+///
+/// ```compile_fail
+/// use erro_rs::errors;
+///
+/// #[errors(no_std, std::io::Error)]
+/// fn foo() {
+///     Ok(())
+/// }
+/// ```
+///
+/// ## `non_exhaustive`
+///
+/// Marks the generated `Error` as `#[non_exhaustive]`, so that `Error`s can be added to a public
+/// function without breaking downstream crates which `match` on it, since they must match it with a
+/// wildcard arm. Within the crate which defines the function, its variants can still be matched
+/// exhaustively:
+///
+/// ```
+/// use erro_rs::errors;
+///
+/// #[errors(non_exhaustive, std::io::Error, std::num::ParseIntError)]
+/// pub fn read_int(path: &str) -> i128 {
+///     Ok(std::fs::read_to_string(path)?.parse()?)
+/// }
+///
+/// match read_int("/this/path/does/not/exist") {
+///     Ok(_) => unreachable!(),
+///     Err(ReadIntError::StdIo(_)) => (),
+///     Err(ReadIntError::StdNumParseInt(_)) => unreachable!(),
+/// }
+/// ```
+///
+/// ## `option`
+///
+/// Adds a `NotFound` variant (or another name, with `option = "..."`) which wraps nothing, for
+/// functions which fail when a value is missing. Its `Display` is derived from its name (or given by
+/// `option_display = "..."`), and it has no `source`. An extension trait is generated alongside it,
+/// whose `or_not_found` (named after the variant) converts `None` into any `Error` which the
+/// generated one can be converted into:
+///
+/// ```
+/// use std::error::Error;
+/// use erro_rs::errors;
+///
+/// struct User {
+///     name: String,
+/// }
+///
+/// #[errors(option, std::num::ParseIntError)]
+/// fn user_name(users: &[User], id: &str) -> String {
+///     let id: usize = id.parse()?;
+///     Ok(users.get(id).or_not_found::<UserNameError>()?.name.clone())
+/// }
+///
+/// #[errors(option = "Missing", option_display = "no users")]
+/// fn first_name(users: &[User]) -> String {
+///     users.first().map(|u| u.name.clone()).ok_or(FirstNameError::Missing)
+/// }
+///
+/// let users = [User {name: "ferris".into()}];
+/// assert_eq!(user_name(&users, "0").unwrap(), "ferris");
+/// assert!(matches!(user_name(&users, "a"), Err(UserNameError::StdNumParseInt(_))));
+///
+/// let err = user_name(&users, "1").unwrap_err();
+/// assert!(matches!(err, UserNameError::NotFound));
+/// assert_eq!(err.to_string(), "not found");
+/// assert!(err.source().is_none());
+///
+/// assert_eq!(first_name(&users).unwrap(), "ferris");
+/// assert_eq!(first_name(&[]).unwrap_err().to_string(), "no users");
+/// ```
+///
+/// Each extension trait is implemented for every `Option`, so two `Error`s in the same scope whose
+/// variants share a name will conflict when their methods are called; call them through the trait
+/// instead (e.g. `UserNameErrorOption::or_not_found(...)`).
+///
+/// Since its variant has no `Error` or fields, it cannot be combined with `backtrace`, `capture`,
+/// `into`, or `location`:
+///
+/// ```compile_fail
+/// use erro_rs::errors;
+///
+/// #[errors(option, location)]
+/// fn first(values: &[u8]) -> u8 {
+///     values.first().copied().ok_or(FirstError::NotFound)
+/// }
+/// ```
+///
+/// ## `order`
+///
+/// With `order = "sorted"`, the variants are declared in the order of their names rather than that
+/// of their entries (`order = "written"`, the default), so that adding an entry anywhere changes the
+/// generated `Error` in the same way. Everything generated from the variants follows that order,
+/// including the numbers of `display_code` and the variants of `kind`:
+///
+/// ```
+/// use erro_rs::errors;
+///
+/// #[errors(order = "sorted", display_code, kind, std::num::ParseIntError, std::io::Error, std::fmt::Error)]
+/// fn first() {
+///     Ok(())
+/// }
+///
+/// #[errors(order = "sorted", display_code, kind, std::fmt::Error, std::num::ParseIntError, std::io::Error)]
+/// fn second() {
+///     Ok(())
+/// }
+///
+/// let kinds = |a: FirstErrorKind, b: SecondErrorKind| assert_eq!((a as u8, format!("{:?}", a)), (b as u8, format!("{:?}", b)));
+/// kinds(FirstErrorKind::StdFmt, SecondErrorKind::StdFmt);
+/// kinds(FirstErrorKind::StdIo, SecondErrorKind::StdIo);
+/// kinds(FirstErrorKind::StdNumParseInt, SecondErrorKind::StdNumParseInt);
+///
+/// let err = FirstError::from(std::fmt::Error);
+/// assert_eq!(err.code(), 0);
+/// assert_eq!(err.to_string(), SecondError::from(std::fmt::Error).to_string());
+/// assert_eq!(SecondError::from("a".parse::<u8>().unwrap_err()).code(), 2);
+/// ```
+///
+/// Since the codes of entries without a `code` count up from the entry before them, an entry which
+/// should keep its code when others are added should be given one.
+///
+/// ## `other`
+///
+/// Adds a catch-all `Other` variant (or another name, with `other = "..."`) which wraps a
+/// `Box<dyn std::error::Error + Send + Sync>`, so that `Error`s which are not listed can still be
+/// propagated once boxed. Its `Display` and `source` forward to the boxed `Error`:
+///
+/// ```
+/// use std::error::Error;
+/// use erro_rs::errors;
+///
+/// #[errors(std::num::ParseIntError, other)]
+/// fn parse(s: &str) -> i128 {
+///     if s.is_empty() {
+///         Err(Box::<dyn Error + Send + Sync>::from("nothing to parse"))?;
+///     }
+///
+///     Ok(s.parse()?)
+/// }
+///
+/// #[errors(other = "Unknown")]
+/// fn unknown() {
+///     Err(Box::<dyn Error + Send + Sync>::from("unknown"))?
+/// }
+///
+/// let err = parse("").unwrap_err();
+/// assert!(matches!(err, ParseError::Other(_)));
+/// assert_eq!(err.to_string(), "nothing to parse");
+/// assert_eq!(err.source().unwrap().to_string(), "nothing to parse");
+///
+/// assert!(matches!(parse("a"), Err(ParseError::StdNumParseInt(_))));
+/// assert!(matches!(unknown(), Err(UnknownError::Unknown(_))));
+/// ```
+///
+/// ## `passthrough`
+///
+/// Merges the `Error` of the `Result` which the function already returns into the generated `Error`,
+/// as if it were listed too. Without it, a function written to return `Result<T, E>` succeeds with
+/// the whole `Result<T, E>` instead:
+///
+/// ```
+/// use erro_rs::errors;
+///
+/// #[derive(Debug)]
+/// struct TooBig;
+///
+/// impl std::fmt::Display for TooBig {
+///     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+///         f.write_str("too big")
+///     }
+/// }
+///
+/// impl std::error::Error for TooBig {}
+///
+/// #[errors(passthrough, std::num::ParseIntError)]
+/// fn parse_digit(s: &str) -> Result<u8, TooBig> {
+///     let n: u8 = s.parse()?;
+///     if n > 9 {
+///         Err(TooBig)?;
+///     }
+///
+///     Ok(n)
+/// }
+///
+/// let _: Result<u8, ParseDigitError> = parse_digit("1");
+/// assert_eq!(parse_digit("7").unwrap(), 7);
+/// assert!(matches!(parse_digit("10"), Err(ParseDigitError::TooBig(_))));
+/// assert!(matches!(parse_digit("a"), Err(ParseDigitError::StdNumParseInt(_))));
+/// ```
+///
+/// If the `Error` is listed as well (e.g. to give it an alias or other options), it is only added once.
+///
+/// Only the last segment of the path is checked, so a `Result` from any crate is recognized, but an
+/// alias of one (e.g. `std::io::Result<T>`) is not; write it out as `Result<T, std::io::Error>`
+/// instead:
+///
+/// ```compile_fail
+/// use erro_rs::errors;
+///
+/// #[errors(passthrough)]
+/// fn read(path: &str) -> std::io::Result<String> {
+///     Ok(std::fs::read_to_string(path)?)
+/// }
+/// ```
+///
+/// ## `report_debug`
+///
+/// Implements [`Debug`] to report the `Display` of the generated `Error` followed by that of each of
+/// its sources, as `anyhow` does, rather than deriving it. This is what `main` prints when it
+/// returns the `Error`:
+///
+/// ```
+/// use erro_rs::errors;
+///
+/// #[errors(report_debug, display_prefix, std::num::ParseIntError)]
+/// fn parse_int(s: &str) -> i128 {
+///     Ok(s.parse()?)
+/// }
+///
+/// #[errors(report_debug, display_prefix = "the config could not be loaded", ParseIntError)]
+/// fn load(s: &str) -> i128 {
+///     Ok(parse_int(s)?)
+/// }
+///
+/// assert_eq!(
+///     format!("{:?}", parse_int("a").unwrap_err()),
+///     "parse_int: invalid digit found in string\n\nCaused by:\n    invalid digit found in string",
+/// );
+///
+/// assert_eq!(format!("{:?}", load("a").unwrap_err()), "\
+/// the config could not be loaded: parse_int: invalid digit found in string
+///
+/// Caused by:
+///     0: parse_int: invalid digit found in string
+///     1: invalid digit found in string");
+/// ```
+///
+/// A single source is reported without a number.
+///
+/// ## `result_alias`
+///
+/// Generates a [`Result`] alias alongside the `Error`, with the same
+/// visibility. Its `Ok` type defaults to the one written on the function:
+///
+/// ```
+/// use erro_rs::errors;
+///
+/// #[errors(result_alias, std::num::ParseIntError)]
+/// fn parse(s: &str) -> i128 {
+///     Ok(s.parse()?)
+/// }
+///
+/// #[errors(result_alias = "Parsed", std::num::ParseIntError)]
+/// fn parse_twice(s: &str) -> i128 {
+///     Ok(s.parse::<i128>()? * 2)
+/// }
+///
+/// let once: ParseResult = parse("2");
+/// let twice: Parsed = parse_twice("2");
+/// let unit: ParseResult<()> = once.map(drop);
+///
+/// assert_eq!(twice.unwrap(), 4);
+/// assert!(unit.is_ok());
+/// ```
+///
+/// ## `send_sync`
+///
+/// Asserts that each `Error` (and the fields of its variant) is [`Send`] and [`Sync`], so that an
+/// `Error` which would keep the generated one from crossing threads is reported at the attribute
+/// argument which lists it, rather than wherever the generated `Error` is sent:
+///
+/// ```
+/// use erro_rs::errors;
+///
+/// #[errors(send_sync, std::io::Error, std::num::ParseIntError)]
+/// fn read_int(path: &str) -> i128 {
+///     Ok(std::fs::read_to_string(path)?.trim().parse()?)
+/// }
+///
+/// let handle = std::thread::spawn(|| read_int("/this/path/does/not/exist"));
+/// assert!(matches!(handle.join().unwrap(), Err(ReadIntError::StdIo(_))));
+/// ```
+///
+/// ```compile_fail
+/// use erro_rs::errors;
+///
+/// #[errors(send_sync, "Box<dyn std::error::Error>" = "Boxed")]
+/// fn foo() {
+///     Ok(())
+/// }
+/// ```
+///
+/// With `send` or `sync`, only one of them is asserted. `Error`s which refer to the generic parameters
+/// of the function cannot be named outside of it, so they are not checked.
+///
+/// ## `serialize`
+///
+/// With the [`serde`](#serde) feature, implements `serde::Serialize` for the generated `Error`. Few
+/// `Error`s implement it themselves, so each is written as the name of its variant and the `Display`
+/// of the `Error` it wraps (and the [`code`](#code) of the variant, if any):
+///
+#[cfg_attr(feature = "serde", doc = "```")]
+#[cfg_attr(not(feature = "serde"), doc = "```ignore")]
+/// use erro_rs::errors;
+///
+/// #[errors(serialize, std::io::Error(code = 2), std::num::ParseIntError)]
+/// fn read_int(path: &str) -> i128 {
+///     Ok(std::fs::read_to_string(path)?.trim().parse()?)
+/// }
+///
+/// let path = std::env::temp_dir().join("erro-rs-serialize");
+/// std::fs::write(&path, "foo").unwrap();
+/// let json = serde_json::to_value(read_int(path.to_str().unwrap()).unwrap_err()).unwrap();
+///
+/// assert_eq!(json, serde_json::json!({
+///     "kind": "StdNumParseInt",
+///     "message": "invalid digit found in string",
+///     "code": 3,
+/// }));
+/// ```
+///
+/// The crate which uses the option must also depend on `serde`.
+///
+/// ## `set`
+///
+/// With `set = ...`, the `Error`s in a set defined by [`error_set!`] are listed in its place. An
+/// `Error` which is in the set and also listed elsewhere is only listed once:
+///
+/// ```
+/// use erro_rs::{error_set, errors};
+///
+/// mod sets {
+///     erro_rs::error_set!(IoAndParse = [std::io::Error, std::num::ParseIntError]);
+/// }
+///
+/// #[errors(set = sets::IoAndParse, std::io::Error, std::fmt::Error)]
+/// fn read_int(path: &str) -> i128 {
+///     Ok(std::fs::read_to_string(path)?.parse()?)
+/// }
+///
+/// fn main() {
+///     match read_int("/this/path/does/not/exist") {
+///         Ok(_) | Err(ReadIntError::StdIo(_) | ReadIntError::StdNumParseInt(_) | ReadIntError::StdFmt(_)) => (),
+///     }
+/// }
+/// ```
+///
+/// The `Error`s can also be listed in brackets, e.g. `set = [std::io::Error]`, which is how the set
+/// passes them back to the macro.
+///
+/// ## `share`
+///
+/// With `share = "..."`, several functions return the same `Error`. It is generated (with the given
+/// name, and the options given alongside it) by the function which lists its `Error`s, and any
+/// other function which is given nothing but the name returns it:
+///
+/// ```
+/// use erro_rs::errors;
+///
+/// #[errors(share = "StoreError", helpers, std::io::Error, std::num::ParseIntError)]
+/// fn load(path: &str) -> i32 {
+///     Ok(std::fs::read_to_string(path)?.parse()?)
+/// }
+///
+/// #[errors(share = "StoreError")]
+/// fn save(path: &str, value: i32) {
+///     Ok(std::fs::write(path, value.to_string())?)
+/// }
+///
+/// fn main() {
+///     let errors: [StoreError; 2] = [
+///         load("/this/path/does/not/exist").unwrap_err(),
+///         save("/this/path/does/not/exist", 1).unwrap_err(),
+///     ];
+///
+///     assert!(errors.iter().all(StoreError::is_std_io));
+/// }
+/// ```
+///
+/// The name refers to the `Error` like any other, so each module may share its own `Error` of the
+/// same name, and a function in another module can return it once it is imported:
+///
+/// ```
+/// mod config {
+///     use erro_rs::errors;
+///
+///     #[errors(share = "StoreError", std::io::Error)]
+///     pub fn load(path: &str) -> String {
+///         Ok(std::fs::read_to_string(path)?)
+///     }
+///
+///     #[errors(share = "StoreError")]
+///     pub fn save(path: &str, value: &str) {
+///         Ok(std::fs::write(path, value)?)
+///     }
+/// }
+///
+/// mod cache {
+///     use erro_rs::errors;
+///
+///     #[errors(share = "StoreError", std::io::Error, std::num::ParseIntError)]
+///     pub fn read_count(path: &str) -> u64 {
+///         Ok(std::fs::read_to_string(path)?.trim().parse()?)
+///     }
+/// }
+///
+/// mod sync {
+///     use erro_rs::errors;
+///     use crate::cache::StoreError;
+///
+///     #[errors(share = "StoreError")]
+///     pub fn refresh(path: &str) -> u64 {
+///         Ok(crate::cache::read_count(path)?)
+///     }
+/// }
+///
+/// fn main() {
+///     assert!(matches!(config::save("/this/path/does/not/exist", ""), Err(config::StoreError::StdIo(_))));
+///     assert!(matches!(sync::refresh("/this/path/does/not/exist"), Err(cache::StoreError::StdIo(_))));
+/// }
+/// ```
+///
+/// Each function which lists the `Error`s generates it, so only one of them may:
+///
+/// ```compile_fail
+/// use erro_rs::errors;
+///
+/// #[errors(share = "StoreError", std::io::Error, std::num::ParseIntError)]
+/// fn load() {
+///     Ok(())
+/// }
+///
+/// #[errors(share = "StoreError", std::io::Error)]
+/// fn save() {
+///     Ok(())
+/// }
+/// ```
+///
+/// ## `skip`
+///
+/// Leaves a method or function as it is, when its `impl` block or module is given `Error`s (see the
+/// remarks). It cannot be combined with any other options:
+///
+/// ```ignore
+/// #[errors(skip)]
+/// ```
+///
+/// ## `strict`
+///
+/// Rejects the listed `Error`s which no `?` in the body of the function propagates, so that variants
+/// are not left behind when the body changes:
+///
+/// ```compile_fail
+/// use erro_rs::errors;
+///
+/// #[errors(strict, std::io::Error, std::num::ParseIntError)]
+/// fn parse_int(s: &str) -> i128 {
+///     Ok(s.parse::<i128>()?)
+/// }
+/// ```
+///
+/// A `?` can only be traced to the `Error` it propagates with the heuristic of `infer`, so the check
+/// only applies when every `?` in the body is recognized, matches a listed `Error`, and the body does
+/// not otherwise convert into the generated `Error` (e.g. with `into`, or by naming it). Otherwise,
+/// nothing is reported:
+///
+/// ```
+/// use erro_rs::errors;
+///
+/// #[errors(strict, std::io::Error, std::num::ParseIntError)]
+/// fn read_int(path: &str) -> i128 {
+///     let content = std::fs::read_to_string(path)?;
+///     Ok(content.trim().parse::<i128>()?)
+/// }
+///
+/// #[errors(strict, std::io::Error, std::num::ParseIntError)]
+/// fn parse_int(s: &str) -> i128 {
+///     s.parse().map_err(ParseIntError::StdNumParseInt)
+/// }
+///
+/// assert!(matches!(read_int("/this/path/does/not/exist"), Err(ReadIntError::StdIo(_))));
+/// assert!(matches!(parse_int("a"), Err(ParseIntError::StdNumParseInt(_))));
+/// ```
+///
+/// `strict` cannot be given to an `impl` block or module, and does nothing with `share`, since the
+/// other functions which share the `Error` may propagate it.
+///
+/// ## `tracing`
+///
+/// With the [`tracing`](#tracing-1) feature, `tracing = "warn"` (or any level accepted by the
+/// [`log`](#log) option) makes each [`From`] implementation of the generated `Error` record the
+/// `Error` it converts as an event, with the `erro_rs` target and the `function` and `variant` it is
+/// converted for as fields:
+///
+#[cfg_attr(feature = "tracing", doc = "```")]
+#[cfg_attr(not(feature = "tracing"), doc = "```ignore")]
+/// use erro_rs::errors;
+///
+/// #[errors(tracing = "debug", std::io::Error, std::num::ParseIntError(level = "warn"))]
+/// fn read_int(path: &str) -> i128 {
+///     Ok(std::fs::read_to_string(path)?.trim().parse()?)
+/// }
+///
+/// assert!(read_int("/this/path/does/not/exist").is_err());
+/// ```
+///
+/// It can be combined with the `log` option, and the crate which uses it must also depend on
+/// `tracing`.
+///
+/// ## `use`
+///
+/// With `use = ...`, no `Error` is generated. Instead, the function returns the given `Error`,
+/// which must already implement [`From`] for each `Error` that `?` is used on. The path is used as
+/// written, so it may be a type alias:
+///
+/// ```
+/// use erro_rs::errors;
+///
+/// mod errors {
+///     pub type ConfigError = Box<dyn std::error::Error>;
+/// }
+///
+/// #[errors(use = crate::errors::ConfigError)]
+/// fn read_port(path: &str) -> u16 {
+///     Ok(std::fs::read_to_string(path)?.parse()?)
+/// }
+///
+/// fn main() {
+///     let err: errors::ConfigError = read_port("/this/path/does/not/exist").unwrap_err();
+///     assert!(err.is::<std::io::Error>());
+/// }
+/// ```
+///
+/// Since there is nothing to generate, `use` cannot be combined with any `Error`s or other options:
+///
+/// ```compile_fail
+/// use erro_rs::errors;
+///
+/// #[errors(use = Box<dyn std::error::Error>, std::io::Error)]
+/// fn foo() {
+///     Ok(())
+/// }
+/// ```
+///
+/// ## `variant_suffix`
+///
+/// Appends to the name derived for each variant, so that they can follow a convention without an
+/// alias for every entry. Variants which are given an alias are named by it alone:
+///
+/// ```
+/// use erro_rs::errors;
+///
+/// #[errors(variant_suffix = "Failed", std::io::Error, std::num::ParseIntError, std::fmt::Error = "Format")]
+/// fn read_int(path: &str) -> i128 {
+///     Ok(std::fs::read_to_string(path)?.trim().parse()?)
+/// }
+///
+/// #[errors(variant_prefix = "Bad", naming = "last", std::num::ParseIntError)]
+/// fn parse_int(s: &str) -> i128 {
+///     Ok(s.parse()?)
+/// }
+///
+/// assert!(matches!(read_int("/this/path/does/not/exist"), Err(ReadIntError::StdIoFailed(_))));
+/// assert!(matches!(ReadIntError::from("a".parse::<i128>().unwrap_err()), ReadIntError::StdNumParseIntFailed(_)));
+/// assert!(matches!(ReadIntError::from(std::fmt::Error), ReadIntError::Format(_)));
+/// assert!(matches!(parse_int("a"), Err(ParseIntError::BadParseInt(_))));
+/// ```
+///
+/// With `variant_prefix`, the name is prepended to instead. The names which result must still be
+/// valid identifiers, and must not collide:
+///
+/// ```compile_fail
+/// use erro_rs::errors;
+///
+/// #[errors(variant_suffix = "Io", std::io::Error = "StdFmtIo", std::fmt::Error)]
+/// fn foo() {
+///     Ok(())
+/// }
+/// ```
+///
+/// ```compile_fail
+/// use erro_rs::errors;
+///
+/// #[errors(variant_suffix = "-Failed", std::io::Error)]
+/// fn foo() {
+///     Ok(())
+/// }
+/// ```
+///
+/// ## `vis`
+///
+/// Overrides the visibility of the generated `Error`, which is otherwise the same as the function's:
+///
+/// ```
+/// mod api {
+///     use erro_rs::errors;
+///
+///     #[errors(vis = "pub", std::io::Error)]
+///     fn helper() {
+///         Ok(())
+///     }
+///
+///     pub fn run() -> Result<(), HelperError> {
+///         helper()
+///     }
+/// }
+///
+/// let result: Result<(), api::HelperError> = api::run();
+/// assert!(result.is_ok());
+/// ```
+///
+/// Any visibility can be given, e.g. `vis = "pub(in crate::api)"`.
+///
+/// ## `wrap`
+///
+/// Rewrites part of the return type to return the generated `Error`, rather than the whole of it.
+/// With `wrap = "future_output"`, the `Output` of a returned `Future` is rewritten, so that the
+/// `Error`s can be returned from an `async` block:
+///
+/// ```
+/// use std::{future::Future, pin::Pin};
+/// use erro_rs::errors;
+///
+/// #[errors(wrap = "future_output", std::io::Error)]
+/// fn load(path: &str) -> Pin<Box<dyn Future<Output = Vec<u8>> + Send + '_>> {
+///     Box::pin(async move { Ok(std::fs::read(path)?) })
+/// }
+///
+/// #[errors(wrap = "future_output", std::num::ParseIntError)]
+/// fn parse(value: String) -> impl Future<Output = u16> {
+///     async move { Ok(value.parse()?) }
+/// }
+///
+/// let _: Pin<Box<dyn Future<Output = Result<Vec<u8>, LoadError>> + Send>> = load("Cargo.toml");
+/// let _: Pin<Box<dyn Future<Output = Result<u16, ParseError>>>> = Box::pin(parse("80".into()));
+/// ```
+///
+/// With `wrap = "item"`, the `Item` of a returned iterator (or `Stream`, or any other trait with an
+/// `Item` binding) is rewritten instead, so that each item may fail:
+///
+/// ```
+/// use erro_rs::errors;
+///
+/// #[errors(wrap = "item", std::num::ParseIntError)]
+/// fn parse_all(line: &str) -> impl Iterator<Item = u8> + '_ {
+///     line.split(',').map(|n| Ok(n.trim().parse()?))
+/// }
+///
+/// let mut numbers = parse_all("1, 2, three");
+/// assert_eq!(numbers.next().unwrap().unwrap(), 1);
+/// assert_eq!(numbers.next().unwrap().unwrap(), 2);
+/// assert!(matches!(numbers.next(), Some(Err(ParseAllError::StdNumParseInt(_)))));
+/// ```
+///
+/// The body of the function is left as it is. If there is nothing to wrap, it is an error:
+///
+/// ```compile_fail
+/// use erro_rs::errors;
+///
+/// #[errors(wrap = "future_output", std::io::Error)]
+/// fn load(path: &str) -> Vec<u8> {
+///     Ok(std::fs::read(path)?)
+/// }
+/// ```
+///
+/// ```compile_fail
+/// use erro_rs::errors;
+///
+/// #[errors(wrap = "item", std::io::Error)]
+/// fn lines(path: &str) -> Vec<String> {
+///     Ok(std::fs::read_to_string(path)?.lines().map(String::from).collect())
+/// }
+/// ```
+///
+/// `wrap` cannot be combined with `capture`, nor given to an `impl` block or module.
+///
+/// # Entry Options
+///
+/// Options which only apply to one `Error` may be passed in parentheses after it, before its alias
+/// (if any). The alias can also be given as an option, e.g. `std::io::Error(alias = "Io")`.
+///
+/// Any number of options can be given, separated by commas:
+///
+/// ```
+/// use erro_rs::errors;
+///
+/// #[errors(std::io::Error(alias = "Io", boxed, code = 2))]
+/// fn read(path: &str) -> String {
+///     Ok(std::fs::read_to_string(path)?)
+/// }
+///
+/// let err = read("/this/path/does/not/exist").unwrap_err();
+/// assert!(matches!(err, ReadError::Io(_)));
+/// assert_eq!(err.code(), 2);
+/// ```
+///
+/// Unknown options, options which are missing their value, and flags which are given one are
+/// rejected:
+///
+/// ```compile_fail
+/// use erro_rs::errors;
+///
+/// #[errors(std::io::Error(boxxed))]
+/// fn foo() {
+///     Ok(())
+/// }
+/// ```
+///
+/// ```compile_fail
+/// use erro_rs::errors;
+///
+/// #[errors(std::io::Error(alias))]
+/// fn foo() {
+///     Ok(())
+/// }
+/// ```
+///
+/// ```compile_fail
+/// use erro_rs::errors;
+///
+/// #[errors(std::io::Error(boxed = true))]
+/// fn foo() {
+///     Ok(())
+/// }
+/// ```
+///
+/// ## `boxed`
+///
+/// With `boxed`, the variant stores the `Error` in a [`Box`], which keeps the generated `Error`
+/// (and every [`Result`] containing it) small even when the `Error` is large. [`From`] does the
+/// boxing:
+///
+/// ```
+/// use std::{fmt, mem};
+/// use erro_rs::errors;
+///
+/// #[derive(Debug)]
+/// struct Giant([u8; 256]);
+///
+/// impl fmt::Display for Giant {
+///     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+///         write!(f, "giant")
+///     }
+/// }
+///
+/// impl std::error::Error for Giant {}
+///
+/// #[errors(Giant(boxed), std::io::Error)]
+/// fn grow() {
+///     Err(Giant([0; 256]))?
+/// }
+///
+/// fn main() {
+///     assert!(mem::size_of::<GrowError>() <= 2 * mem::size_of::<usize>());
+///
+///     let err = grow().unwrap_err();
+///     assert_eq!(err.to_string(), "giant");
+///     assert!(std::error::Error::source(&err).unwrap().is::<Giant>());
+/// }
+/// ```
+///
+/// ## `cfg`
+///
+/// With `cfg(...)`, the variant is only compiled when the given predicate holds, e.g.
+/// `sqlx::Error(cfg(feature = "postgres"))`. Everything generated for the variant (its `Display`,
+/// [`From`] implementation, helpers, and so on) is conditionally compiled along with it, so the
+/// `Error` it wraps need not exist otherwise:
+///
+/// ```
+/// use erro_rs::errors;
+///
+/// #[errors(errors_doc, helpers, kind, postgres::Error(cfg(any())), std::io::Error(cfg(all())))]
+/// fn connect(path: &str) -> String {
+///     Ok(std::fs::read_to_string(path)?)
+/// }
+///
+/// fn main() {
+///     let err = connect("/this/path/does/not/exist").unwrap_err();
+///     assert!(err.is_std_io());
+///     assert_eq!(err.kind(), ConnectErrorKind::StdIo);
+/// }
+/// ```
+///
+/// The generated `Error` still compiles when every variant is compiled out:
+///
+/// ```
+/// use erro_rs::errors;
+///
+/// #[errors(display_code, helpers, kind, location, postgres::Error(cfg(any()), fields(query: String), exit_code = 2))]
+/// fn connect() {
+///     Ok(())
+/// }
+///
+/// fn main() {
+///     assert!(connect().is_ok());
+/// }
+/// ```
+///
+/// ## `code`
+///
+/// With `code = ...`, the variant is given a numeric code, which is returned by a `code` method of
+/// the generated `Error` (and a `const` `code` method of its [`kind`](#kind), if any). Like the
+/// discriminants of an `enum`, variants without a code are given the code after the one before
+/// them (or 0, if they are first):
+///
+/// ```
+/// use erro_rs::errors;
+///
+/// #[errors(kind, std::io::Error(code = 10), std::num::ParseIntError, std::fmt::Error(code = 20))]
+/// fn read_int(path: &str) -> i128 {
+///     Ok(std::fs::read_to_string(path)?.parse()?)
+/// }
+///
+/// const CODE: u32 = ReadIntErrorKind::StdNumParseInt.code();
+///
+/// fn main() {
+///     assert_eq!(read_int("/this/path/does/not/exist").unwrap_err().code(), 10);
+///     assert_eq!(CODE, 11);
+///     assert_eq!(ReadIntErrorKind::StdFmt.code(), 20);
+/// }
+/// ```
+///
+/// Codes must be unique:
+///
+/// ```compile_fail
+/// use erro_rs::errors;
+///
+/// #[errors(std::io::Error(code = 11), std::num::ParseIntError(code = 11))]
+/// fn read_int(path: &str) -> i128 {
+///     Ok(std::fs::read_to_string(path)?.parse()?)
+/// }
+/// ```
+///
+/// ## `defmt`
+///
+/// With the [`defmt`](#defmt) option, the `Error` is written with its own `defmt::Format`
+/// implementation, rather than through `defmt::Debug2Format`:
+///
+/// ```compile_fail
+/// use erro_rs::errors;
+///
+/// // the `defmt` entry option requires the `defmt` option
+/// #[errors(std::io::Error(defmt))]
+/// fn foo() {
+///     Ok(())
+/// }
+/// ```
+///
+/// ## `diagnostic_source`
+///
+/// With the [`diagnostic`](#diagnostic) option, the `Error` is returned as the `diagnostic_source`
+/// of the variant, so it must implement `miette::Diagnostic` too. Without it, the variant has no
+/// `diagnostic_source`.
+///
+/// ## `display`
+///
+/// With `display = "debug"`, the generated [`Display`](std::fmt::Display) uses the
+/// [`Debug`](std::fmt::Debug) implementation of the `Error` instead of its `Display`
+/// implementation. This allows wrapping types which only implement `Debug`, though since they
+/// cannot implement [`Error`](std::error::Error), the variant has no
+/// [`source`](std::error::Error::source):
+///
+/// ```
+/// use erro_rs::errors;
+///
+/// mod weird_sys {
+///     #[derive(Debug)]
+///     pub struct Error(pub i32);
+/// }
+///
+/// #[errors(weird_sys::Error(display = "debug"), std::io::Error)]
+/// fn call() {
+///     Err(weird_sys::Error(-1))?
+/// }
+///
+/// let err = call().unwrap_err();
+/// assert_eq!(err.to_string(), "Error(-1)");
+/// assert!(std::error::Error::source(&err).is_none());
+/// ```
+///
+/// ## `exit_code`
+///
+/// With `exit_code = ...`, the process exits with the given code when it fails with the variant.
+/// If any variant has an exit code, an `exit_code` method is generated, and the `Error` can be
+/// converted into an [`ExitCode`](std::process::ExitCode). Variants without an exit code exit with
+/// 1:
+///
+/// ```
+/// use std::process::ExitCode;
+/// use erro_rs::errors;
+///
+/// #[errors(std::io::Error(exit_code = 66), std::num::ParseIntError)]
+/// fn read_int(path: &str) -> i128 {
+///     Ok(std::fs::read_to_string(path)?.parse()?)
+/// }
+///
+/// let err = read_int("/this/path/does/not/exist").unwrap_err();
+/// assert_eq!(err.exit_code(), 66);
+/// assert_eq!(ExitCode::from(err), ExitCode::from(66));
+///
+/// let err = ReadIntError::from("a".parse::<i128>().unwrap_err());
+/// assert_eq!(err.exit_code(), 1);
+/// ```
+///
+/// Exit codes must be within `0..=255`:
+///
+/// ```compile_fail
+/// use erro_rs::errors;
+///
+/// #[errors(std::io::Error(exit_code = 256))]
+/// fn read(path: &str) -> String {
+///     Ok(std::fs::read_to_string(path)?)
+/// }
+/// ```
+///
+/// ## `fields`
+///
+/// With `fields(...)`, the variant becomes a struct with the `Error` as its `source` field, followed
+/// by the given fields. Since [`From`] cannot fill in those fields, the variant is instead built
+/// with a constructor named after it in `snake_case`:
+///
+/// ```
+/// use std::{error::Error, io, path::{Path, PathBuf}};
+/// use erro_rs::errors;
+///
+/// #[errors(std::io::Error(fields(path: PathBuf)), std::num::ParseIntError)]
+/// fn read_int(path: &Path) -> i128 {
+///     let content = std::fs::read_to_string(path).map_err(|e| ReadIntError::std_io(e, path.into()))?;
+///     Ok(content.parse()?)
+/// }
+///
+/// let err = read_int("/this/path/does/not/exist".as_ref()).unwrap_err();
+/// match err {
+///     ReadIntError::StdIo { ref source, ref path } => {
+///         assert_eq!(source.kind(), io::ErrorKind::NotFound);
+///         assert_eq!(path, Path::new("/this/path/does/not/exist"));
+///     },
+///     ReadIntError::StdNumParseInt(_) => unreachable!(),
+/// }
+///
+/// assert_eq!(err.to_string(), err.source().unwrap().to_string());
+/// ```
+///
+/// An extension trait named after the `Error` (e.g. `ReadIntErrorContext`) is also generated, which
+/// converts the `Error` of a `Result` into the variant with a `ctx_*` method:
+///
+/// ```
+/// use std::path::PathBuf;
+/// use erro_rs::errors;
+///
+/// #[errors(std::io::Error(fields(path: PathBuf)), std::num::ParseIntError)]
+/// fn read_int(path: &str) -> i128 {
+///     Ok(std::fs::read_to_string(path).ctx_std_io(path)?.parse()?)
+/// }
+///
+/// assert!(matches!(
+///     read_int("/this/path/does/not/exist"),
+///     Err(ReadIntError::StdIo { path, .. }) if path == PathBuf::from("/this/path/does/not/exist"),
+/// ));
+/// ```
+///
+/// Fields can be documented like any other:
+///
+/// ```
+/// #![deny(missing_docs)]
+/// //! A crate.
+///
+/// use erro_rs::errors;
+///
+/// /// Read from a file.
+/// #[errors(std::io::Error(fields(
+///     /// The path of the file.
+///     path: std::path::PathBuf,
+/// )))]
+/// pub fn read(path: &str) -> String {
+///     std::fs::read_to_string(path).map_err(|e| ReadError::std_io(e, path.into()))
+/// }
+///
+/// fn main() {}
+/// ```
+///
+/// ## `help`
+///
+/// With the [`diagnostic`](#diagnostic) option, `help = "..."` is the `help` of the variant:
+///
+/// ```compile_fail
+/// use erro_rs::errors;
+///
+/// // `help` requires `diagnostic`
+/// #[errors(std::io::Error(help = "check the path"))]
+/// fn foo() {
+///     Ok(())
+/// }
+/// ```
+///
+/// ## `level`
+///
+/// With the [`log`](#log) or [`tracing`](#tracing) options, `level = "..."` is the level at which
+/// conversions into the variant are logged, instead of the level given to the option (or implied
+/// by its [`severity`](#severity)):
+///
+/// ```compile_fail
+/// use erro_rs::errors;
+///
+/// // `level` requires the `log` or `tracing` option
+/// #[errors(std::io::Error(level = "error"))]
+/// fn foo() {
+///     Ok(())
+/// }
+/// ```
+///
+/// ## `no_bound`
+///
+/// Each `Error` is asserted to implement [`Error`](std::error::Error), so that listing a type which
+/// is not one (e.g. `std::io::ErrorKind`) is reported at the attribute argument:
+///
+/// ```compile_fail
+/// use erro_rs::errors;
+///
+/// #[errors(std::io::ErrorKind)]
+/// fn foo() {
+///     Ok(())
+/// }
+/// ```
+///
+/// With `no_bound`, a type which deliberately does not implement `Error` (e.g. `anyhow::Error`) can be
+/// wrapped, as long as it implements [`Display`](std::fmt::Display). Its variant has no
+/// [`source`](std::error::Error::source):
+///
+/// ```
+/// use erro_rs::errors;
+///
+/// #[derive(Debug)]
+/// struct Report(String);
+///
+/// impl std::fmt::Display for Report {
+///     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+///         f.write_str(&self.0)
+///     }
+/// }
+///
+/// #[errors(Report(no_bound))]
+/// fn report() {
+///     Err(Report("failed".into()))?
+/// }
+///
+/// let err = report().unwrap_err();
+/// assert_eq!(err.to_string(), "failed");
+/// assert!(std::error::Error::source(&err).is_none());
+/// ```
+///
+/// It cannot be combined with `transparent`, which forwards to the `source` of the `Error`.
+///
+/// ## `no_from`
+///
+/// With `no_from`, [`From`] is not implemented for the `Error`, so the variant must be constructed
+/// explicitly. This allows the same `Error` to be wrapped by more than one variant, as long as only
+/// one of them implements `From`:
+///
+/// ```
+/// use erro_rs::errors;
+///
+/// #[errors(std::io::Error = "ReadFailed", std::io::Error(alias = "WriteFailed", no_from))]
+/// fn copy(from: &str, to: &str) {
+///     let content = std::fs::read(from)?;
+///     std::fs::write(to, content).map_err(CopyError::WriteFailed)
+/// }
+///
+/// assert!(matches!(copy("/this/path/does/not/exist", "/tmp/copy"), Err(CopyError::ReadFailed(_))));
+/// assert!(matches!(copy(file!(), "/this/path/does/not/exist"), Err(CopyError::WriteFailed(_))));
+/// ```
+///
+/// ```compile_fail
+/// use erro_rs::errors;
+///
+/// #[errors(std::io::Error = "ReadFailed", std::io::Error = "WriteFailed")]
+/// fn copy(from: &str, to: &str) {
+///     Ok(())
+/// }
+/// ```
+///
+/// ## `opaque`
+///
+/// Some `Error`s (e.g. `anyhow::Error`) do not implement [`Error`](std::error::Error) themselves,
+/// but refer to one through [`AsRef<dyn Error + Send + Sync>`](AsRef). With `opaque`, the variant
+/// is still displayed with the `Display` of the `Error`, but its `source` is the `Error` referred
+/// to:
+///
+/// ```
+/// use erro_rs::errors;
+///
+/// #[errors(anyhow::Error(opaque), std::io::Error)]
+/// fn load(path: &str) -> String {
+///     let config = std::fs::read_to_string(path)?;
+///     match config.is_empty() {
+///         true => Err(anyhow::anyhow!("the config is empty"))?,
+///         false => Ok(config),
+///     }
+/// }
+///
+/// let err = LoadError::from(anyhow::anyhow!("the config is empty"));
+/// assert_eq!(err.to_string(), "the config is empty");
+/// assert_eq!(std::error::Error::source(&err).unwrap().to_string(), "the config is empty");
+/// ```
+///
+/// Without it, such an `Error` is reported at the attribute argument which lists it. To give the
+/// variant no `source` at all, use [`no_bound`](#no_bound) instead.
+///
+/// ```compile_fail
+/// use erro_rs::errors;
+///
+/// #[errors(anyhow::Error)]
+/// fn foo() {
+///     Ok(())
+/// }
+/// ```
+///
+/// ## `retryable`
+///
+/// With `retryable`, the operation which failed with the variant may succeed if it is tried again.
+/// If any variant is retryable, an `is_retryable` method is generated (on the kind of the `Error`
+/// too, with the [`kind`](#kind) option), so that retry policies can live with the `Error`:
+///
+/// ```
+/// use std::{cell::Cell, io};
+/// use erro_rs::errors;
+///
+/// #[errors(kind, std::io::Error(retryable), std::num::ParseIntError)]
+/// fn fetch(attempts: &Cell<u8>) -> i128 {
+///     attempts.set(attempts.get() + 1);
+///     match attempts.get() {
+///         1 | 2 => Err(io::Error::from(io::ErrorKind::TimedOut))?,
+///         _ => Ok("42".parse()?),
+///     }
+/// }
+///
+/// let attempts = Cell::new(0);
+/// let result = loop {
+///     match fetch(&attempts) {
+///         Err(e) if e.is_retryable() => continue,
+///         result => break result,
+///     }
+/// };
+///
+/// assert_eq!(result.unwrap(), 42);
+/// assert_eq!(attempts.get(), 3);
+///
+/// let err = FetchError::from("a".parse::<i128>().unwrap_err());
+/// assert!(!err.is_retryable());
+/// assert!(!err.kind().is_retryable());
+/// assert!(FetchErrorKind::StdIo.is_retryable());
+/// ```
+///
+/// ## `severity`
+///
+/// With `severity = "warn"` (or `"error"`, or `"critical"`), the variant is given that severity. If
+/// any variant has a severity, a `severity` method is generated, which returns the name of the
+/// severity of each variant. Variants without a severity are `"error"`:
+///
+/// ```
+/// use erro_rs::errors;
+///
+/// #[errors(std::io::Error(severity = "critical"), std::num::ParseIntError(severity = "warn"), std::fmt::Error)]
+/// fn read_int(path: &str) -> i128 {
+///     Ok(std::fs::read_to_string(path)?.trim().parse()?)
+/// }
+///
+/// assert_eq!(read_int("/this/path/does/not/exist").unwrap_err().severity(), "critical");
+/// assert_eq!(ReadIntError::from("a".parse::<i128>().unwrap_err()).severity(), "warn");
+/// assert_eq!(ReadIntError::from(std::fmt::Error).severity(), "error");
+/// ```
+///
+/// With the [`log`](#log) or [`tracing`](#tracing) options, conversions into the variant are
+/// logged at the level of its severity (`"warn"` or `"error"`) instead of the level given to the
+/// option, unless it is given a [`level`](#level):
+///
+#[cfg_attr(feature = "log", doc = "```")]
+#[cfg_attr(not(feature = "log"), doc = "```ignore")]
+/// use std::sync::Mutex;
+/// use erro_rs::errors;
+///
+/// static EVENTS: Mutex<Vec<String>> = Mutex::new(Vec::new());
+///
+/// struct Logger;
+///
+/// impl log::Log for Logger {
+///     fn enabled(&self, _: &log::Metadata) -> bool {
+///         true
+///     }
+///
+///     fn log(&self, record: &log::Record) {
+///         EVENTS.lock().unwrap().push(format!("{} {}", record.level(), record.args()));
+///     }
+///
+///     fn flush(&self) {}
+/// }
+///
+/// #[errors(
+///     log = "info",
+///     std::io::Error(severity = "critical"),
+///     std::num::ParseIntError(severity = "warn"),
+///     std::fmt::Error(severity = "warn", level = "debug"),
+///     std::str::Utf8Error,
+/// )]
+/// fn convert() {
+///     Ok(())
+/// }
+///
+/// log::set_logger(&Logger).unwrap();
+/// log::set_max_level(log::LevelFilter::Trace);
+///
+/// ConvertError::from(std::io::Error::from(std::io::ErrorKind::NotFound));
+/// ConvertError::from("a".parse::<i128>().unwrap_err());
+/// ConvertError::from(std::fmt::Error);
+/// ConvertError::from(String::from_utf8(vec![0xff]).unwrap_err().utf8_error());
+///
+/// assert_eq!(*EVENTS.lock().unwrap(), [
+///     "ERROR convert: entity not found",
+///     "WARN convert: invalid digit found in string",
+///     "DEBUG convert: an error occurred when formatting an argument",
+///     "INFO convert: invalid utf-8 sequence of 1 bytes from index 0",
+/// ]);
+/// ```
+///
+/// ```compile_fail
+/// use erro_rs::errors;
+///
+/// #[errors(std::io::Error(severity = "fatal"))]
+/// fn foo() {
+///     Ok(())
+/// }
+/// ```
+///
+/// ## `status`
+///
+/// With `status = ...`, the variant is given an HTTP status code. If any variant has a status code,
+/// a `status_code` method is generated, which is left to the web framework to turn into a response
+/// (unless [`into_response`](#into_response) is given). Variants without a status code are 500:
+///
+/// ```
+/// use erro_rs::errors;
+///
+/// #[derive(Debug)]
+/// struct Unauthorized;
+///
+/// impl std::fmt::Display for Unauthorized {
+///     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+///         f.write_str("unauthorized")
+///     }
+/// }
+///
+/// impl std::error::Error for Unauthorized {}
+///
+/// #[errors(std::io::Error, std::num::ParseIntError(status = 422), Unauthorized(status = 401))]
+/// fn handle(token: &str, body: &str) -> i128 {
+///     if token.is_empty() {
+///         Err(Unauthorized)?;
+///     }
+///
+///     Ok(body.parse()?)
+/// }
+///
+/// assert_eq!(handle("", "1").unwrap_err().status_code(), 401);
+/// assert_eq!(handle("token", "a").unwrap_err().status_code(), 422);
+/// assert_eq!(HandleError::from(std::io::Error::from(std::io::ErrorKind::Other)).status_code(), 500);
+/// ```
+///
+/// Status codes must be within `100..=599`:
+///
+/// ```compile_fail
+/// use erro_rs::errors;
+///
+/// #[errors(std::io::Error(status = 600))]
+/// fn foo() {
+///     Ok(())
+/// }
+/// ```
+///
+/// ## `transparent`
+///
+/// With `transparent`, the [`Display`](std::fmt::Display) and
+/// [`source`](std::error::Error::source) of the variant forward to the `Error` it wraps, as if the
+/// variant were not there. This is useful for `Error`s generated by other `#[errors]` functions:
+///
+/// ```
+/// use std::error::Error;
+/// use erro_rs::errors;
+///
+/// #[errors(std::io::Error)]
+/// fn load(path: &str) -> String {
+///     Ok(std::fs::read_to_string(path)?)
+/// }
+///
+/// #[errors(display_prefix, LoadError(transparent), std::num::ParseIntError)]
+/// fn read_int(path: &str) -> i128 {
+///     Ok(load(path)?.parse()?)
+/// }
+///
+/// #[errors(LoadError)]
+/// fn read(path: &str) -> String {
+///     Ok(load(path)?)
+/// }
+///
+/// fn depth(err: &(dyn Error + 'static)) -> usize {
+///     std::iter::successors(err.source(), |&e| e.source()).count()
+/// }
+///
+/// let inner = load("/this/path/does/not/exist").unwrap_err();
+/// let transparent = read_int("/this/path/does/not/exist").unwrap_err();
+/// let opaque = read("/this/path/does/not/exist").unwrap_err();
+///
+/// assert_eq!(transparent.to_string(), inner.to_string());
+/// assert_eq!(format!("{:#}", transparent), format!("{:#}", inner));
+/// assert_eq!(depth(&transparent), depth(&inner));
+/// assert_eq!(depth(&opaque), depth(&inner) + 1);
+/// ```
+///
+/// # Features
+///
+/// ## `axum`
+///
+/// Allows the [`into_response`](#into_response) option. Without this feature, the option is
+/// rejected.
+///
+/// ## `defmt`
+///
+/// Allows the [`defmt`](#defmt) option. Without this feature, the option is rejected.
+///
+/// ## `log`
+///
+/// Allows the [`log`](#log) option. Without this feature, the option is rejected.
+///
+/// ## `miette`
+///
+/// Allows the [`diagnostic`](#diagnostic) option. Without this feature, the option is rejected.
+///
+/// ## `nightly-provide`
+///
+/// Implements [`Error::provide`](std::error::Error) for the generated `Error`, which provides the
+/// [`backtrace`](#backtrace) (if any) and then delegates to the `Error` being wrapped. This requires
+/// a nightly compiler, and `#![feature(error_generic_member_access)]` in every crate which uses the
+/// macro. Without this feature, nothing about the generated code changes:
+///
+#[cfg_attr(feature = "nightly-provide", doc = "```")]
+#[cfg_attr(not(feature = "nightly-provide"), doc = "```ignore")]
+/// #![feature(error_generic_member_access)]
+///
+/// use std::{backtrace::Backtrace, error};
+/// use erro_rs::errors;
+///
+/// #[errors(backtrace, std::io::Error)]
+/// fn read(path: &str) -> String {
+///     Ok(std::fs::read_to_string(path)?)
+/// }
+///
+/// fn main() {
+///     let err = read("/this/path/does/not/exist").unwrap_err();
+///     let backtrace = error::request_ref::<Backtrace>(&err).unwrap();
+///     assert!(std::ptr::eq(backtrace, err.backtrace()));
+/// }
+/// ```
+///
+/// ## `serde`
+///
+/// Allows the [`serialize`](#serialize) option. Without this feature, the option is rejected.
+///
+/// ## `tracing`
+///
+/// Allows the [`tracing`](#tracing) option. Without this feature, the option is rejected.
+///
+/// # Remarks
+///
+/// Each variant of the generated `Error` is documented with the `Error` that it wraps, so the macro
+/// can be used on public functions in crates which `#![deny(missing_docs)]`:
+///
+/// ```
+/// #![deny(missing_docs)]
+/// //! A crate.
+///
+/// use erro_rs::errors;
+///
+/// /// Read an integer from a file.
+/// #[errors(std::io::Error, std::num::ParseIntError = "Parse", "Box<dyn std::error::Error>" = "Other")]
+/// pub fn read_int(path: &str) -> i128 {
+///     Ok(std::fs::read_to_string(path)?.parse()?)
+/// }
+///
+/// fn main() {
+///     assert!(read_int("/this/path/does/not/exist").is_err());
+/// }
+/// ```
+///
+/// When formatted with `{:#}`, the [`Display`](std::fmt::Display) of the generated `Error` is
+/// followed by that of each [`source`](std::error::Error::source) after the `Error` it wraps,
+/// separated by `": "`:
+///
+/// ```
+/// use std::{error::Error, fmt, io};
+/// use erro_rs::errors;
+///
+/// #[derive(Debug)]
+/// struct Context(&'static str, Box<dyn Error + Send + Sync>);
+///
+/// impl fmt::Display for Context {
+///     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+///         f.write_str(self.0)
+///     }
+/// }
+///
+/// impl Error for Context {
+///     fn source(&self) -> Option<&(dyn Error + 'static)> {
+///         Some(&*self.1)
+///     }
+/// }
+///
+/// #[errors(Context)]
+/// fn load() {
+///     let io = io::Error::new(io::ErrorKind::NotFound, "no such file");
+///     let section = Context("failed to read the `server` section", io.into());
+///     Err(Context("failed to load the configuration", section.into()))?
+/// }
+///
+/// let err = load().unwrap_err();
+/// assert_eq!(format!("{}", err), "failed to load the configuration");
+/// assert_eq!(
+///     format!("{:#}", err),
+///     "failed to load the configuration: failed to read the `server` section: no such file",
+/// );
+/// ```
+///
+/// The macro may be attached to a function more than once (e.g. with `cfg_attr`), in which case the
+/// arguments of each are listed after those of the first, as though they were given to one macro:
+///
+/// ```
+/// use erro_rs::errors;
+///
+/// #[errors(std::io::Error)]
+/// #[cfg_attr(all(), errors(helpers, std::num::ParseIntError))]
+/// #[cfg_attr(any(), errors(reqwest::Error))]
+/// fn read_int(path: &str) -> i128 {
+///     Ok(std::fs::read_to_string(path)?.parse()?)
+/// }
+///
+/// fn main() {
+///     assert!(read_int("/this/path/does/not/exist").unwrap_err().is_std_io());
+/// }
+/// ```
+///
+/// ```compile_fail
+/// use erro_rs::errors;
+///
+/// #[errors(std::io::Error)]
+/// #[errors(std::io::Error)]
+/// fn foo() {
+///     Ok(())
+/// }
+/// ```
+///
+/// The variants of the generated `Error` are declared in the same order as the `Error`s passed to
+/// the macro, unless it is given `order = "sorted"`.
+///
+/// The visibility of the generated `Error` will be the same as the function which it is attached
+/// to, unless overridden by `vis`. If the function is `pub(crate)`, the `Error` will be
+/// `pub(crate)` also.
+///
+/// The name of the function also determines the name of the generated `Error`. See the example.
+/// The `r#` of a function named by a raw identifier is left out, as it is for the paths of the
+/// `Error`s:
+///
+/// ```
+/// use erro_rs::errors;
+///
+/// mod r#mod {
+///     pub type Error = std::num::ParseIntError;
+/// }
+///
+/// #[errors(display_prefix, r#mod::Error)]
+/// fn r#try(s: &str) -> i128 {
+///     Ok(s.parse()?)
+/// }
+///
+/// let err = r#try("a").unwrap_err();
+/// assert!(matches!(err, TryError::Mod(_)));
+/// assert_eq!(err.to_string(), "try: invalid digit found in string");
+/// ```
+///
+/// An `Error` cannot be defined inside of an `impl` block, so to use the macro on methods, attach an
+/// `#[errors]` to the `impl` block too. Each method's `Error` is generated before the block, and is
+/// named after both the type and the method (so that methods of the same name on other types do not
+/// collide). Any `Self` in its `Error`s refers to the type being implemented:
+///
+/// ```
+/// use std::{convert::TryFrom, sync::Arc};
+/// use erro_rs::errors;
+///
+/// #[derive(Debug)]
+/// struct Config(String);
+///
+/// #[errors]
+/// impl Config {
+///     #[errors(std::io::Error)]
+///     fn load(path: &str) -> Self {
+///         Ok(Self(std::fs::read_to_string(path)?))
+///     }
+///
+///     #[errors(capture(self), std::num::ParseIntError)]
+///     fn port(&self) -> u16 {
+///         Ok(self.0.trim().parse()?)
+///     }
+///
+///     #[errors(std::num::TryFromIntError)]
+///     fn len(self: Arc<Self>) -> u8 {
+///         Ok(u8::try_from(self.0.len())?)
+///     }
+///
+///     fn clear(&mut self) {
+///         self.0.clear();
+///     }
+/// }
+///
+/// assert!(matches!(Config::load("/this/path/does/not/exist"), Err(ConfigLoadError::StdIo(_))));
+///
+/// let err = Config("eighty".into()).port().unwrap_err();
+/// assert_eq!(err.captured_self(), r#"Config("eighty")"#);
+/// assert_eq!(Arc::new(Config("80".into())).len().unwrap(), 2);
+/// ```
+///
+/// Only the last segment of the type's path is used, without its generic arguments:
+///
+/// ```
+/// use erro_rs::errors;
+///
+/// struct Config;
+/// struct Cache<T>(Vec<T>);
+///
+/// #[errors]
+/// impl Config {
+///     #[errors(std::io::Error)]
+///     fn load(path: &str) -> Self {
+///         std::fs::read(path)?;
+///         Ok(Config)
+///     }
+/// }
+///
+/// #[errors]
+/// impl<T> Cache<T> {
+///     #[errors(std::fmt::Error, name = "CacheFailure")]
+///     fn load(values: &[T]) -> usize {
+///         Ok(values.len())
+///     }
+///
+///     #[errors(std::io::Error)]
+///     fn clear(&mut self) {
+///         self.0.clear();
+///         Ok(())
+///     }
+/// }
+///
+/// assert!(matches!(Config::load("/this/path/does/not/exist"), Err(ConfigLoadError::StdIo(_))));
+/// let loaded: Result<usize, CacheFailure> = Cache::load(&[1, 2]);
+/// assert_eq!(loaded.unwrap(), 2);
+///
+/// let cleared: Result<(), CacheClearError> = Cache(vec![1]).clear();
+/// assert!(cleared.is_ok());
+/// ```
+///
+/// ```compile_fail
+/// use erro_rs::errors;
+///
+/// struct Config;
+///
+/// impl Config {
+///     #[errors(std::io::Error)]
+///     fn load(&self) {
+///         Ok(())
+///     }
+/// }
+/// ```
+///
+/// `Error`s may also be given to the `impl` block itself, in which case one `Error` (named after the
+/// type) is generated for the whole block, and every method is rewritten to return it. Methods which
+/// have their own `#[errors(...)]` still return their own `Error`, and methods which are given
+/// `skip` (or which already return the block's `Error`) are left as they are:
+///
+/// ```
+/// use erro_rs::errors;
+///
+/// #[derive(Debug, Default)]
+/// struct Config(String);
+///
+/// #[errors(result_alias, std::io::Error, std::num::ParseIntError)]
+/// impl Config {
+///     #[errors(skip)]
+///     fn new() -> Self {
+///         Self::default()
+///     }
+///
+///     fn load(path: &str) -> Self {
+///         Ok(Self(std::fs::read_to_string(path)?))
+///     }
+///
+///     fn port(&self) -> u16 {
+///         Ok(self.0.trim().parse()?)
+///     }
+///
+///     fn load_port(path: &str) -> ConfigResult<u16> {
+///         Self::load(path)?.port()
+///     }
+///
+///     #[errors(std::fmt::Error)]
+///     fn name(&self) -> String {
+///         Ok(self.0.clone())
+///     }
+/// }
+///
+/// assert_eq!(Config::new().0, "");
+/// assert!(matches!(Config::load_port("/this/path/does/not/exist"), Err(ConfigError::StdIo(_))));
+/// assert!(matches!(Config("eighty".into()).port(), Err(ConfigError::StdNumParseInt(_))));
+/// assert_eq!(Config("app".into()).name().unwrap(), "app");
+/// ```
+///
+/// The block's `Error` is as visible as the most visible method which returns it. `capture` and
+/// `share` cannot be given to an `impl` block, and `skip` cannot be combined with anything else:
+///
+/// ```compile_fail
+/// use erro_rs::errors;
+///
+/// struct Config;
+///
+/// #[errors(std::io::Error)]
+/// impl Config {
+///     #[errors(skip, std::fmt::Error)]
+///     fn new() -> Self {
+///         Config
+///     }
+/// }
+/// ```
+///
+/// `Error`s may be given to a module in the same way, in which case its `Error` is named after the
+/// module and generated inside of it. Every function in the module is rewritten to return it, except
+/// those which have their own `#[errors(...)]` (as above):
+///
+/// ```
+/// use erro_rs::errors;
+///
+/// #[errors(std::io::Error, std::num::ParseIntError)]
+/// mod config {
+///     use erro_rs::errors;
+///
+///     pub fn load(path: &str) -> String {
+///         Ok(std::fs::read_to_string(path)?)
+///     }
+///
+///     pub fn port(path: &str) -> u16 {
+///         Ok(load(path)?.trim().parse()?)
+///     }
+///
+///     #[errors(skip)]
+///     pub fn default_port() -> u16 {
+///         8080
+///     }
+///
+///     pub mod env {
+///         pub fn port() -> Option<u16> {
+///             std::env::var("PORT").ok()?.parse().ok()
+///         }
+///     }
+/// }
+///
+/// assert!(matches!(config::port("/this/path/does/not/exist"), Err(config::ConfigError::StdIo(_))));
+/// assert_eq!(config::default_port(), 8080);
+/// assert_eq!(config::env::port().unwrap_or(8080), 8080);
+/// ```
+///
+/// Nested modules are left as they are, unless the module is given `nested` (see the options). The
+/// module's contents must be written inline:
+///
+/// ```compile_fail
+/// use erro_rs::errors;
+///
+/// #[errors(std::io::Error)]
+/// mod config;
+/// ```
+///
+/// The same goes for the methods of a trait, whether they are provided or required. Since a trait's
+/// methods may share names with free functions, the name of the trait is prefixed to their `Error`
+/// (which is as visible as the trait):
+///
+/// ```
+/// use erro_rs::errors;
+///
+/// #[errors]
+/// trait Store: Sized {
+///     fn path(&self) -> &str;
+///
+///     #[errors(std::io::Error)]
+///     fn load(&self) -> Vec<u8> {
+///         Ok(std::fs::read(self.path())?)
+///     }
+///
+///     #[errors(std::fmt::Error)]
+///     fn merge(self, other: Self) -> Self;
+/// }
+///
+/// struct Missing;
+///
+/// impl Store for Missing {
+///     fn path(&self) -> &str {
+///         "/this/path/does/not/exist"
+///     }
+///
+///     fn merge(self, _: Self) -> Result<Self, StoreMergeError> {
+///         Ok(self)
+///     }
+/// }
+///
+/// assert!(matches!(Missing.load(), Err(StoreLoadError::StdIo(_))));
+/// ```
+///
+/// The `Self` of a trait is unknown, so the `Error`s of its methods cannot refer to it:
+///
+/// ```compile_fail
+/// use erro_rs::errors;
+///
+/// #[errors]
+/// trait Parse: std::str::FromStr {
+///     #[errors(<Self as std::str::FromStr>::Err = "Parse")]
+///     fn parse(s: &str) -> Self;
+/// }
+/// ```
+///
+/// The same is true in an `impl` of a trait. The rewritten method must still match the trait, so
+/// the trait should declare it as returning `Error`s which each `impl` can choose (e.g. with an
+/// associated type):
+///
+/// ```
+/// use erro_rs::errors;
+///
+/// trait Store {
+///     type Error;
+///     fn load(&self) -> Result<Vec<u8>, Self::Error>;
+/// }
+///
+/// struct File(String);
+///
+/// #[errors]
+/// impl Store for File {
+///     type Error = FileLoadError;
+///
+///     #[errors(std::io::Error)]
+///     fn load(&self) -> Vec<u8> {
+///         Ok(std::fs::read(&self.0)?)
+///     }
+/// }
+///
+/// let file = File("/this/path/does/not/exist".into());
+/// assert!(matches!(file.load(), Err(FileLoadError::StdIo(_))));
+/// ```
+///
+/// Otherwise, the method is reported as returning an `Error` which its trait cannot name (unless
+/// the `Error` is given a `name`, which the trait may name itself):
+///
+/// ```compile_fail
+/// use erro_rs::errors;
+///
+/// trait Named {
+///     fn name(&self) -> String;
+/// }
+///
+/// struct File(String);
+///
+/// #[errors]
+/// impl Named for File {
+///     #[errors(std::fmt::Error)]
+///     fn name(&self) -> String {
+///         Ok(self.0.clone())
+///     }
+/// }
+/// ```
+///
+/// Traits and `impl` blocks which use [`async_trait`](https://docs.rs/async-trait) are supported, as
+/// long as `#[errors]` is attached above `#[async_trait]`, so that each method is rewritten before
+/// its body is boxed:
+///
+/// ```
+/// use std::{future::Future, sync::Arc, task::{Context, Poll, Wake, Waker}};
+/// use async_trait::async_trait;
+/// use erro_rs::errors;
+///
+/// #[async_trait]
+/// trait Store {
+///     type Error;
+///     async fn load(&self) -> Result<Vec<u8>, Self::Error>;
+/// }
+///
+/// struct File(String);
+///
+/// #[errors]
+/// #[async_trait]
+/// impl Store for File {
+///     type Error = FileLoadError;
+///
+///     #[errors(std::io::Error)]
+///     async fn load(&self) -> Vec<u8> {
+///         Ok(std::fs::read(&self.0)?)
+///     }
+/// }
+///
+/// struct Wakeless;
+///
+/// impl Wake for Wakeless {
+///     fn wake(self: Arc<Self>) {}
+/// }
+///
+/// fn block_on<F: Future>(future: F) -> F::Output {
+///     let waker = Waker::from(Arc::new(Wakeless));
+///     match Box::pin(future).as_mut().poll(&mut Context::from_waker(&waker)) {
+///         Poll::Ready(output) => output,
+///         Poll::Pending => unreachable!("the future never waits"),
+///     }
+/// }
+///
+/// assert!(matches!(block_on(File("/this/path/does/not/exist".into()).load()), Err(FileLoadError::StdIo(_))));
+/// assert!(block_on(File("Cargo.toml".into()).load()).is_ok());
+/// ```
+///
+/// Otherwise, `#[async_trait]` has already rewritten the method's body to return its original type,
+/// which is reported:
+///
+/// ```compile_fail
+/// use async_trait::async_trait;
+/// use erro_rs::errors;
+///
+/// #[async_trait]
+/// trait Store {
+///     async fn load(&self) -> Result<Vec<u8>, StoreLoadError>;
+/// }
+///
+/// struct File(String);
+///
+/// #[async_trait]
+/// #[errors]
+/// impl Store for File {
+///     #[errors(std::io::Error, name = "StoreLoadError")]
+///     async fn load(&self) -> Vec<u8> {
+///         Ok(std::fs::read(&self.0)?)
+///     }
+/// }
+/// ```
+///
+/// The macro can only be attached to functions, `impl` blocks, modules, and traits. Using it on any
+/// other item is an error:
+///
+/// ```compile_fail
+/// use erro_rs::errors;
+///
+/// #[errors(std::io::Error)]
+/// struct Foo;
+/// ```
+///
+/// # Example
+///
+/// ```
+/// use std::{fmt, fs, path::Path};
+/// use erro_rs::errors;
+///
+/// match read_int("/tmp/foo") {
+///     Ok(i) => println!("Was an `Ok`: {}", i),
+///     Err(ReadIntError::StdIo(e)) => println!("`io::Error`: {}", e),
+///     Err(ReadIntError::StdNumParseInt(e)) => println!("`ParseIntError`: {}", e),
+/// };
+///
+/// #[errors(std::io::Error, std::num::ParseIntError)]
+/// fn read_int(path: impl AsRef<Path>) -> i128 {
+///     let content = fs::read_to_string(path)?;
+///     let number = content.parse::<i128>()?;
+///     Ok(number)
+/// }
+/// ```
+///
+/// The above is equivalent to the following:
+///
+/// ```
+/// use std::{error::Error, fmt, fs, io, num::ParseIntError, path::Path};
+///
+/// match read_int("/tmp/foo") {
+///     Ok(i) => println!("Was an `Ok`: {}", i),
+///     Err(ReadIntError::StdIo(e)) => println!("`io::Error`: {}", e),
+///     Err(ReadIntError::StdNumParseInt(e)) => println!("`ParseIntError`: {}", e),
+/// };
+///
+/// fn read_int(path: impl AsRef<Path>) -> Result<i128, ReadIntError> {
+///     let content = fs::read_to_string(path)?;
+///     let number = content.parse::<i128>()?;
+///     Ok(number)
+/// }
+///
+/// #[derive(Debug)]
+/// enum ReadIntError {
+///     StdIo(io::Error),
+///     StdNumParseInt(ParseIntError),
+/// }
+///
+/// impl fmt::Display for ReadIntError {
+///     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+///         match self {
+///             Self::StdIo(e) => write!(f, "{}", e),
+///             Self::StdNumParseInt(e) => write!(f, "{}", e),
+///         }
+///     }
+/// }
+///
+/// impl From<io::Error> for ReadIntError {
+///     fn from(e: io::Error) -> Self {
+///         Self::StdIo(e)
+///     }
+/// }
+///
+/// impl From<ParseIntError> for ReadIntError {
+///     fn from(e: ParseIntError) -> Self {
+///         Self::StdNumParseInt(e)
+///     }
+/// }
+///
+/// impl Error for ReadIntError {
+///     fn source(&self) -> Option<&(dyn Error + 'static)> {
+///         match self {
+///             Self::StdIo(e) => Some(e),
+///             Self::StdNumParseInt(e) => Some(e),
+///         }
+///     }
+/// }
+/// ```
+///
+/// The [`source`](std::error::Error::source) of each variant is the `Error` it wraps:
+///
+/// ```
+/// use std::{error::Error, io};
+/// use erro_rs::errors;
+///
+/// #[errors(std::io::Error, std::num::ParseIntError)]
+/// fn read_int(path: &str) -> i128 {
+///     let content = std::fs::read_to_string(path)?;
+///     let number = content.parse::<i128>()?;
+///     Ok(number)
+/// }
+///
+/// let err = read_int("/this/path/does/not/exist").unwrap_err();
+/// assert!(matches!(err, ReadIntError::StdIo(_)));
+///
+/// let source = err.source().unwrap();
+/// assert!(source.downcast_ref::<io::Error>().is_some());
+/// assert!(source.source().is_none());
+/// ```
+#[proc_macro_attribute]
+pub fn errors(attr: TokenStream, item: TokenStream) -> TokenStream
+{
+	expand_errors(attr.into(), item.into()).into()
+}
+
+/// The expansion of the `#[errors]` macro with the arguments `attr` on the `item`.
+fn expand_errors(attr: TokenStream2, item: TokenStream2) -> TokenStream2
+{
+	let mut item = match syn::parse2::<Item>(item.clone())
+	{
+		Ok(item @ Item::Fn(_)) | Ok(item @ Item::Impl(_)) | Ok(item @ Item::Mod(_)) | Ok(item @ Item::Trait(_)) => item,
+		Ok(other) => return syn::Error::new_spanned(other, NOT_A_FN).into_compile_error(),
+		Err(_) => return syn::Error::new_spanned(item, NOT_A_FN).into_compile_error(),
+	};
+
+	let attr = match item
+	{
+		Item::Fn(ref mut function) => stacked(attr, &mut function.attrs),
+		Item::Impl(ref mut block) => stacked(attr, &mut block.attrs),
+		Item::Mod(ref mut module) => stacked(attr, &mut module.attrs),
+		Item::Trait(ref mut definition) => stacked(attr, &mut definition.attrs),
+		_ => unreachable!(),
+	};
+
+	let args = match syn::parse2::<Args>(attr.clone())
+	{
+		Ok(args) => args,
+		Err(e) => return e.into_compile_error(),
+	};
+
+	if let Some(ref set) = args.set
+	{
+		// The set passes its `Error`s back to this macro.
+		return set::call(set, attr, item.into_token_stream());
+	}
+
+	let debug = args.debug;
+	let expanded = match item
+	{
+		Item::Fn(function) => parse_fn(function.attrs, function.vis, function.sig, Some(*function.block), args, Scope::Free)
+			.map(|(error, function)| quote!(#error #function)),
+		Item::Impl(block) => scope::expand_impl(block, attr),
+		Item::Mod(module) => scope::expand_mod(module, attr),
+		Item::Trait(definition) => scope::expand_trait(definition, attr),
+		_ => unreachable!(),
+	}.unwrap_or_else(syn::Error::into_compile_error);
+
+	if debug
+	{
+		eprintln!("`#[errors(debug)]` expanded to:\n{}\n", expanded);
+	}
+
+	expanded
+}
+
+/// The arguments of the `#[errors]` macro, `attr`, followed by those of any other `#[errors]`
+/// attributes in the `attrs` of the function (which are removed).
+fn stacked(attr: TokenStream2, attrs: &mut Vec<Attribute>) -> TokenStream2
+{
+	let mut args: Vec<_> = std::iter::once(attr).filter(|a| !a.is_empty()).collect();
+	attrs.retain(|a|
+	{
+		if !is_errors(a)
+		{
+			return true;
+		}
+
+		// An attribute without arguments adds nothing.
+		args.extend(a.parse_args::<TokenStream2>().ok().filter(|a| !a.is_empty()));
+		false
+	});
+
+	quote!(#(#args),*)
+}
+
+/// Whether the `attr` is the `#[errors]` macro.
+fn is_errors(attr: &Attribute) -> bool
+{
+	let segments: Vec<_> = attr.path.segments.iter().map(|s| s.ident.to_string()).collect();
+	segments == ["errors"] || segments == ["erro_rs", "errors"]
+}
+
+/// # Summary
+///
+/// This macro defines a named set of [errors](std::error::Error), which can be given to the
+/// [`errors`](macro@errors) macro with its `set` option:
+///
+/// ```
+/// use erro_rs::{error_set, errors};
+///
+/// error_set!(IoAndParse = [std::io::Error, std::num::ParseIntError]);
+///
+/// #[errors(set = IoAndParse, std::fmt::Error)]
+/// fn read_int(path: &str) -> i128 {
+///     Ok(std::fs::read_to_string(path)?.parse()?)
+/// }
+///
+/// fn main() {
+///     assert!(matches!(read_int("/this/path/does/not/exist"), Err(ReadIntError::StdIo(_))));
+/// }
+/// ```
+///
+/// # Remarks
+///
+/// The set is a `macro_rules!` of the same name, so it can be used anywhere in the crate after it
+/// is defined (e.g. as `crate::errors::IoAndParse`), but not outside of the crate.
+///
+/// The `Error`s of the set are checked where it is defined:
+///
+/// ```compile_fail
+/// use erro_rs::error_set;
+///
+/// error_set!(Invalid = ["std::sync::mpsc::SendError<"]);
+/// ```
+#[proc_macro]
+pub fn error_set(input: TokenStream) -> TokenStream
+{
+	syn::parse_macro_input!(input as ErrorSet).expand().into()
+}
+
+/// # Summary
+///
+/// This macro emits the items which the [`errors`](macro@errors) macro generated for a function
+/// given its `defer` option, wherever it is called:
+///
+/// ```
+/// use erro_rs::{emit_errors, errors};
+///
+/// emit_errors!(read_int);
 ///
-/// You can override the default naming by assigning the `Error` an alias:
+/// #[errors(defer, std::io::Error, std::num::ParseIntError)]
+/// fn read_int(path: &str) -> i128 {
+///     Ok(std::fs::read_to_string(path)?.trim().parse()?)
+/// }
 ///
-/// ```ignore
-/// #[errors(bincode::Error, std::io::Error = "IoError")]
+/// fn main() {
+///     assert!(matches!(read_int("/this/path/does/not/exist"), Err(ReadIntError::StdIo(_))));
+/// }
 /// ```
 ///
 /// # Remarks
 ///
-/// The visibility of the generated `Error` will be the same as the function which it is attached
-/// to. If the function is `pub(crate)`, the `Error` will be `pub(crate)` also.
-///
-/// The name of the function also determines the name of the generated `Error`. See the example.
+/// The items are held by a `macro_rules!` named after the function (e.g. `read_int_errors`), so
+/// the function can be named by its path from anywhere in the crate (e.g.
+/// `emit_errors!(crate::files::read_int)`), but not outside of the crate. Each function's items must
+/// be emitted once.
+#[proc_macro]
+pub fn emit_errors(input: TokenStream) -> TokenStream
+{
+	syn::parse_macro_input!(input as Emit).expand().into()
+}
+
+/// # Summary
 ///
-/// # Example
+/// This macro declares the [error](std::error::Error) which the [`errors`](macro@errors) macro
+/// would generate, without a function to return it (e.g. for closures or trait objects):
 ///
 /// ```
-/// use std::{fmt, fs, path::Path};
-/// use erro_rs::errors;
-///
-/// match read_int("/tmp/foo") {
-///     Ok(i) => println!("Was an `Ok`: {}", i),
-///     Err(ReadIntError::StdIo(e)) => println!("`io::Error`: {}", e),
-///     Err(ReadIntError::StdNumParseInt(e)) => println!("`ParseIntError`: {}", e),
-/// };
+/// use erro_rs::errors_enum;
 ///
-/// #[errors(std::io::Error, std::num::ParseIntError)]
-/// fn read_int(path: impl AsRef<Path>) -> i128 {
-///     let content = fs::read_to_string(path)?;
-///     let number = content.parse::<i128>()?;
-///     Ok(number)
-/// }
-/// ```
+/// errors_enum!(pub ConfigError: std::io::Error, std::num::ParseIntError = "Parse");
 ///
-/// The above is equivalent to the following:
+/// let load = |path: &str| -> Result<i128, ConfigError> {
+///     Ok(std::fs::read_to_string(path)?.trim().parse()?)
+/// };
 ///
+/// assert!(matches!(load("/this/path/does/not/exist"), Err(ConfigError::StdIo(_))));
+/// assert_eq!(ConfigError::from("a".parse::<i128>().unwrap_err()).to_string(), "invalid digit found in string");
+/// assert!(matches!("a".parse::<i128>().map_err(ConfigError::from), Err(ConfigError::Parse(_))));
 /// ```
-/// use std::{error::Error, fmt, fs, io, num::ParseIntError, path::Path};
 ///
-/// match read_int("/tmp/foo") {
-///     Ok(i) => println!("Was an `Ok`: {}", i),
-///     Err(ReadIntError::StdIo(e)) => println!("`io::Error`: {}", e),
-///     Err(ReadIntError::StdNumParseInt(e)) => println!("`ParseIntError`: {}", e),
-/// };
+/// # Remarks
 ///
-/// fn read_int(path: impl AsRef<Path>) -> Result<i128, ReadIntError> {
-///     let content = fs::read_to_string(path)?;
-///     let number = content.parse::<i128>()?;
-///     Ok(number)
-/// }
+/// The name of the `Error` is preceded by its visibility, and followed by the same options and
+/// entries as the `errors` macro, except for those which describe how a function is rewritten
+/// (e.g. `capture`, `infer`, and `wrap`):
 ///
-/// #[derive(Debug)]
-/// enum ReadIntError {
-///     StdIo(io::Error),
-///     StdNumParseInt(ParseIntError),
-/// }
+/// ```
+/// mod config {
+///     erro_rs::errors_enum!(pub(crate) LoadError: kind, result_alias, std::io::Error = "Io");
+///     erro_rs::errors_enum!(ParseError: std::num::ParseIntError);
 ///
-/// impl fmt::Display for ReadIntError {
-///     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-///         match self {
-///             Self::StdIo(e) => write!(f, "{}", e),
-///             Self::StdNumParseInt(e) => write!(f, "{}", e),
-///         }
+///     pub(crate) fn parse(s: &str) -> bool {
+///         s.parse::<i128>().map_err(ParseError::from).is_ok()
 ///     }
 /// }
 ///
-/// impl From<io::Error> for ReadIntError {
-///     fn from(e: io::Error) -> Self {
-///         Self::StdIo(e)
-///     }
-/// }
+/// let result: config::LoadResult<()> = Err(std::io::Error::other("missing").into());
+/// assert_eq!(result.unwrap_err().kind(), config::LoadErrorKind::Io);
+/// assert!(!config::parse("a"));
+/// ```
 ///
-/// impl From<ParseIntError> for ReadIntError {
-///     fn from(e: ParseIntError) -> Self {
-///         Self::StdNumParseInt(e)
-///     }
+/// The visibility is that of the `Error`, so one without it cannot be named outside of its module:
+///
+/// ```compile_fail
+/// mod config {
+///     erro_rs::errors_enum!(ParseError: std::num::ParseIntError);
 /// }
 ///
-/// impl Error for ReadIntError {}
+/// let _: Option<config::ParseError> = None;
 /// ```
-#[proc_macro_attribute]
-pub fn errors(attr: TokenStream, item: TokenStream) -> TokenStream
+#[proc_macro]
+pub fn errors_enum(input: TokenStream) -> TokenStream
+{
+	syn::parse_macro_input!(input as Standalone).expand().unwrap_or_else(syn::Error::into_compile_error).into()
+}
+
+/// The message reported when `#[errors]` is attached to something other than a function.
+const NOT_A_FN: &str = "the #[errors] macro can only be used on functions, `impl` blocks, modules, and traits";
+
+/// The message reported when `#[errors]` is expanded after `#[async_trait]`.
+const ASYNC_TRAIT: &str = "the #[errors] macro must be attached above #[async_trait], so that it rewrites each method before #[async_trait] does";
+
+/// The message reported when `#[errors]` is attached to a method, rather than the `impl` block or
+/// trait which contains it.
+const METHOD: &str = "the #[errors] macro cannot define an `Error` inside of an `impl` block or trait; attach #[errors] to the `impl` block or trait as well";
+
+/// Rewrite the function with the `attrs`, `vis`, `sig`, and `block` (if it has one) to return the
+/// `Error` described by the `args`, which is generated alongside it.
+///
+/// The `Error` and the function are returned separately, since the `Error` cannot always be defined
+/// where the function is (e.g. in an `impl` block).
+fn parse_fn(
+	attrs: Vec<Attribute>,
+	vis: Visibility,
+	mut sig: Signature,
+	block: Option<Block>,
+	mut args: Args,
+	scope: Scope,
+) -> syn::Result<(TokenStream2, TokenStream2)>
 {
-	let attr_args = syn::parse_macro_input!(attr as AttributeArgs);
-	let attr_args_parsed: HashMap<_, _> = attr_args.into_iter().filter_map(|a| match a
+	// `#[async_trait]` names the lifetime of the `Future` it boxes the body into, and the body has
+	// already been rewritten to return the original type by then.
+	if sig.generics.lifetimes().any(|l| l.lifetime.ident == "async_trait")
 	{
-		NestedMeta::Meta(Meta::Path(p)) => Some((p, None)),
-		NestedMeta::Meta(Meta::NameValue(v)) => Some((v.path, Some(v.lit))),
-		_ => None,
-	}).collect();
+		return Err(syn::Error::new_spanned(&sig.ident, ASYNC_TRAIT));
+	}
+
+	if let (Scope::Free, Some(receiver)) = (scope, sig.receiver())
+	{
+		return Err(syn::Error::new_spanned(receiver, METHOD));
+	}
+
+	if let (Scope::Impl(_) | Scope::Trait(_), Some(defer)) = (scope, &args.defer)
+	{
+		return Err(syn::Error::new_spanned(defer, "`defer` cannot be given to a method, since `emit_errors!` can only name free functions"));
+	}
+
+	if let Some(ref skip) = args.skip
+	{
+		return Err(syn::Error::new_spanned(skip, "`skip` can only be used in an `impl` block or module which is given `Error`s"));
+	}
+
+	let ident = sig.ident.clone();
+	let path = scope.path(&ident);
+	let mut returned = ok_type(&mut sig);
+	let output = match args.wrap
+	{
+		Some(wrap) => match wrap.find(&mut returned)
+		{
+			Some(output) => output.clone(),
+			None => return Err(syn::Error::new_spanned(&returned, wrap.missing())),
+		},
+		None => returned.clone(),
+	};
+
+	if let (true, Some(arg)) = (args.declare_only, args.capture.first())
+	{
+		return Err(syn::Error::new_spanned(arg, "`capture` cannot be combined with `declare_only`, since the body is left as it is"));
+	}
+
+	if args.declare_only && args.passthrough
+	{
+		return Err(syn::Error::new_spanned(&output, "`passthrough` cannot be combined with `declare_only`, which expects the function to return the generated `Error` already"));
+	}
+
+	// The signature already names the generated `Error`, which is checked once it is named.
+	let (output, declared) = match args.declare_only
+	{
+		true => match split_result(&output)
+		{
+			Some((ok, err)) => (ok, Some(err)),
+			None => return Err(syn::Error::new_spanned(&output, "`declare_only` requires the function to return a `Result<T, E>`, whose `E` is the generated `Error`")),
+		},
+		false => (output, None),
+	};
+
+	// The `Error` which the function already returns becomes one more entry.
+	let output = match args.passthrough
+	{
+		true =>
+		{
+			let (ok, err) = split_result(&output).ok_or_else(|| syn::Error::new_spanned(&output,
+				"`passthrough` requires the function to return a `Result<T, E>`, whose `E` is merged into the generated `Error`",
+			))?;
+
+			if !args.entries.iter().any(|e| written(&e.spec) == written(&err))
+			{
+				args.entries.push(Entry::found(err));
+			}
+
+			ok
+		},
+		false => output,
+	};
+
+	if args.infer
+	{
+		let block = block.as_ref().ok_or_else(|| syn::Error::new_spanned(&sig.ident, "`infer` cannot be used on a function without a body"))?;
+
+		// Any `?`s which were not recognized are assumed to be covered by the listed `Error`s.
+		let (inferred, unknown) = infer::entries(block, args.wrap == Some(Wrap::FutureOutput));
+		if args.entries.is_empty() && !unknown.is_empty()
+		{
+			let message = "the `Error` propagated by this `?` cannot be inferred; list it alongside `infer`";
+			let mut errors = unknown.iter().map(|t| syn::Error::new_spanned(t, message));
+			let mut error = errors.next().expect("`unknown` should not be empty");
+			errors.for_each(|e| error.combine(e));
+			return Err(error);
+		}
+
+		for err in inferred
+		{
+			if !args.entries.iter().any(|e| written(&e.spec) == written(&err.spec))
+			{
+				args.entries.push(err);
+			}
+		}
+	}
+
+	if let (Some(_), Some(arg)) = (args.wrap, args.capture.first())
+	{
+		return Err(syn::Error::new_spanned(arg, "`capture` cannot be combined with `wrap`, since the body does not return the `Error`s itself"));
+	}
+
+	if let Some(ref existing) = args.existing
+	{
+		// Only the signature is rewritten; the `Error` and its conversions already exist.
+		let block = block.map_or_else(|| quote!(;), |b| b.into_token_stream());
+		sig.output = returning(returned, args.wrap, quote_spanned!(output.span()=> core::result::Result<#output, #existing>));
+		return Ok((TokenStream2::new(), quote!
+		{
+			#(#attrs)*
+			#vis #sig
+			#block
+		}));
+	}
+
+	let generics = generics::merged(scope.generics(), &sig.generics);
+	let error_generics = generics::used(&mut args.entries, &generics);
+	let name = scope.name(&ident);
+	if let (Some(name), Some(_)) = (&args.name, &args.share)
+	{
+		return Err(syn::Error::new_spanned(name, "`name` cannot be combined with `share`, which names the `Error` already"));
+	}
+
+	if let (Some(_), Some(share)) = (&args.module, &args.share)
+	{
+		return Err(syn::Error::new_spanned(share, "`module` cannot be combined with `share`, since the functions which share the `Error` may be in different modules"));
+	}
+
+	let error_ident = args.share.clone().or_else(|| args.name.clone()).unwrap_or_else(|| format_ident!("{}Error", name));
+	entry::sort(&mut args)?;
+	let error = ErrorEnum::new(&args, path.clone(), error_generics, error_ident, scope.vis().unwrap_or(&vis).clone())?;
+	let error_ident = &error.ident;
+	let error_ty = error.ty();
+	if let Some(declared) = declared
+	{
+		let declared_ident = match declared
+		{
+			Type::Path(ref ty) if ty.qself.is_none() => ty.path.segments.last().map(|s| &s.ident),
+			_ => None,
+		};
+
+		if declared_ident != Some(error_ident)
+		{
+			return Err(syn::Error::new_spanned(declared, format!(
+				"expected the function to return `{}`, which is the `Error` generated for it", error_ident,
+			)));
+		}
+	}
+
+	let error_vis = &error.vis;
+
+	let result_alias = args.result_alias.as_ref().map(|alias|
+	{
+		let alias = alias.clone().unwrap_or_else(|| format_ident!("{}Result", name));
+		let doc = format!(
+			"The [`Result`](std::result::Result) of [`{}`], which may fail with a [`{}`].",
+			path, error_ident,
+		);
+
+		// Type aliases do not enforce bounds, so only the names of the parameters are given. The
+		// `output` can only be the default if it does not refer to any others (or to the `Self` of a
+		// trait).
+		let lifetimes: Vec<_> = error.generics.lifetimes().map(|l| &l.lifetime).collect();
+		let params: Vec<_> = error.generics.type_params().map(|p| &p.ident).collect();
+		let fn_lifetimes: Vec<_> = generics.lifetimes().map(|l| &l.lifetime.ident).collect();
+		let fn_params: Vec<_> = generics.type_params().map(|p| &p.ident).collect();
+		let default = scope.resolve(output.to_token_stream()).ok()
+			.filter(|o| generics::mentions(o.clone(), &fn_params).iter().all(|p| params.contains(p)))
+			.filter(|o| generics::mentions_lifetimes(o.clone(), &fn_lifetimes).iter().all(|l| lifetimes.iter().any(|u| u.ident == **l)))
+			.map(|o| quote!(= #o));
+
+		let t = generics::fresh("T", &error.generics);
+
+		quote!
+		{
+			#[doc = #doc]
+			#error_vis type #alias<#(#lifetimes,)* #(#params,)* #t #default> = std::result::Result<#t, #error_ty>;
+		}
+	});
+
+	let fn_errors_doc = args.errors_doc.then(|| errors_doc(&attrs, &error, &ident));
+
+	// A shared `Error` may be propagated by the other functions which share it.
+	if let (true, None, Some(block)) = (args.strict, &args.share, &block)
+	{
+		let alias = args.result_alias.clone().flatten().unwrap_or_else(|| format_ident!("{}Result", name));
+		let unused = infer::unused(block, args.wrap == Some(Wrap::FutureOutput), &args.entries, &[error_ident, &alias]);
+		let mut errors = unused.into_iter().map(|e| syn::Error::new_spanned(e.spec.source(), format!(
+			"`{}` is listed, but no `?` in `{}` propagates it; remove it, or remove `strict`", written(e.spec.source()), path,
+		)));
+
+		if let Some(mut error) = errors.next()
+		{
+			errors.for_each(|e| error.combine(e));
+			return Err(error);
+		}
+	}
 
-	if let Ok(function) = syn::parse::<ItemFn>(item)
+	// Without `emit_errors!`, the `Error` cannot be found, which is reported where it was deferred.
+	let returned_error = match args.defer
 	{
-		return parse_fn(function, attr_args_parsed);
+		Some(ref defer) => defer::respan(error_ty.clone(), defer.span()),
+		None => error_ty.clone(),
+	};
+
+	let result = quote_spanned!(output.span()=> core::result::Result<#output, #returned_error>);
+	let block = match (block, args.capture.first())
+	{
+		(Some(block), None) => block.into_token_stream(),
+		(Some(block), Some(_)) => capture(&args, &sig, &block, result.clone())?,
+		(None, None) => quote!(;),
+		(None, Some(arg)) => return Err(syn::Error::new_spanned(arg, "`capture` cannot be used on a function without a body")),
+	};
+
+	if args.share.is_some()
+	{
+		if let Some(arg) = args.capture.first()
+		{
+			return Err(syn::Error::new_spanned(arg, "`capture` cannot be combined with `share`, since the arguments of each function differ"));
+		}
+
+		if !error.generics.params.is_empty()
+		{
+			return Err(syn::Error::new_spanned(&error.generics, "`share` cannot be used with `Error`s which refer to the generic parameters of the function"));
+		}
 	}
 
-	panic!("The #[errors] macro can only be used on functions");
+	let error_items = error.expand();
+
+	sig.output = match args.declare_only
+	{
+		true => syn::parse_quote!(-> #returned),
+		false => returning(returned, args.wrap, result),
+	};
+
+	let items = error.declare(quote!
+	{
+		#error_items
+		#result_alias
+	});
+
+	let items = match args.no_std
+	{
+		true => expand::without_std(items),
+		false => items,
+	};
+
+	Ok((
+		match args.defer
+		{
+			Some(_) => defer::define(&ident, items),
+			None => items,
+		},
+		quote!
+		{
+			#(#attrs)* #fn_errors_doc
+			#vis #sig
+			#block
+		},
+	))
 }
 
-fn parse_fn(function: ItemFn, errors: HashMap<Path, Option<Lit>>) -> TokenStream
+/// The `T` and `E` of the `ty`, if it is a `Result<T, E>`.
+///
+/// Only the last segment of the path is checked, so a `Result` from any crate is recognized, but an
+/// alias of one (e.g. `io::Result<T>`) is not.
+pub(crate) fn split_result(ty: &Type) -> Option<(Type, Type)>
 {
-	let attrs = function.attrs;
-	let block = function.block;
-	let vis = function.vis;
+	let args = match ty
+	{
+		Type::Group(group) => return split_result(&group.elem),
+		Type::Paren(paren) => return split_result(&paren.elem),
+		Type::Path(path) if path.qself.is_none() => match path.path.segments.last()
+		{
+			Some(last) if last.ident == "Result" => match last.arguments
+			{
+				PathArguments::AngleBracketed(ref args) => &args.args,
+				_ => return None,
+			},
+			_ => return None,
+		},
+		_ => return None,
+	};
+
+	let mut types = args.iter().map(|arg| match arg
+	{
+		GenericArgument::Type(ty) => Some(ty.clone()),
+		_ => None,
+	});
+
+	match (types.next(), types.next(), types.next())
+	{
+		(Some(Some(ok)), Some(Some(err)), None) => Some((ok, err)),
+		_ => None,
+	}
+}
 
-	let constness = function.sig.constness;
-	let asyncness = function.sig.asyncness;
-	let unsafety = function.sig.unsafety;
-	let abi = function.sig.abi;
-	let fn_token = function.sig.fn_token;
-	let ident = function.sig.ident;
-	let generics = function.sig.generics.clone();
-	let where_clause = function.sig.generics.where_clause;
-	let inputs = function.sig.inputs.into_iter();
-	let variadic = function.sig.variadic;
-	let output = match function.sig.output
+/// The type which the function with the `sig` returns when it succeeds, which is removed from the
+/// `sig` so that it can be rewritten.
+fn ok_type(sig: &mut Signature) -> Type
+{
+	match std::mem::replace(&mut sig.output, ReturnType::Default)
 	{
 		ReturnType::Default => Tuple(TypeTuple
 		{
@@ -148,64 +4418,230 @@ fn parse_fn(function: ItemFn, errors: HashMap<Path, Option<Lit>>) -> TokenStream
 			elems: Punctuated::new(),
 		}),
 		ReturnType::Type(_, t) => *t,
+	}
+}
+
+/// The return type of a function which `returned` some type, after it is rewritten to return the
+/// `result` (in place of the type which the `wrap` option finds, if it was given).
+fn returning(mut returned: Type, wrap: Option<Wrap>, result: TokenStream2) -> ReturnType
+{
+	let wrapped = match wrap
+	{
+		Some(wrap) => wrap.find(&mut returned),
+		None => None,
 	};
 
-	let error_doc = format!("The [error](std::error::Error) returned by [`{}`]", ident);
-	let error_ident = format_ident!("{}Error", ident.to_string().to_camel_case());
-	let error_variants = errors.iter().map(|(err, alias)| format_ident!("{}",
-		if let Some(Lit::Str(alias)) = alias
+	match wrapped
+	{
+		Some(output) =>
 		{
-			alias.value()
-		}
-		else
+			*output = syn::parse_quote!(#result);
+			syn::parse_quote!(-> #returned)
+		},
+		None => syn::parse_quote!(-> #result),
+	}
+}
+
+/// The "Errors" section appended to the documentation (the `attrs`) of the function called `ident`
+/// by the `errors_doc` option, which lists each variant of the `error`.
+fn errors_doc(attrs: &[Attribute], error: &ErrorEnum, ident: &Ident) -> TokenStream2
+{
+	let mut lines = Vec::with_capacity(error.variants.len() + 3);
+	if attrs.iter().any(|a| a.path.is_ident("doc"))
+	{
+		lines.push(String::new());
+	}
+
+	lines.push("# Errors".into());
+	lines.push(String::new());
+	if error.variants.is_empty()
+	{
+		lines.push("This function does not currently fail.".into());
+	}
+
+	// `clippy::missing_errors_doc` ignores docs which are spanned at the macro expansion.
+	let lines = lines.into_iter().map(|l| LitStr::new(&l, ident.span()));
+	let entries = error.args.entries.iter().zip(error.entry_docs()).zip(&error.variants).map(|((err, ty), variant)|
+	{
+		let line = match err.spec
+		{
+			Spec::Unit(_) => format!(" - [`{}::{}`] — returned in place of {}", error.ident, variant, ty),
+			_ => format!(" - [`{}::{}`] — propagated from {}", error.ident, variant, ty),
+		};
+
+		let line = LitStr::new(&line, ident.span());
+		match err.cfg
 		{
-			err.segments.iter().map(|s| s.ident.to_string().replace("Error", "").to_camel_case()).collect()
+			// The variant being linked to may not exist.
+			Some(ref predicate) => quote_spanned!(ident.span()=> #[cfg_attr(#predicate, doc = #line)]),
+			None => quote_spanned!(ident.span()=> #[doc = #line]),
 		}
-	));
+	});
 
-	let errors_one = errors.keys();
-	let errors_two = errors.keys();
-	let error_variants_two = error_variants.clone();
-	let error_variants_three = error_variants.clone();
+	quote_spanned!(ident.span()=> #(#[doc = #lines])* #(#entries)*)
+}
+
+/// Wrap the `block` of a function so that the arguments named by the `capture` option are stored in
+/// any `Error` it returns.
+fn capture(args: &Args, sig: &Signature, block: &Block, output: TokenStream2) -> syn::Result<TokenStream2>
+{
+	if let Some(ref constness) = sig.constness
+	{
+		return Err(syn::Error::new_spanned(constness, "`capture` cannot be used on a `const fn`"));
+	}
+
+	let params: Vec<_> = sig.inputs.iter().filter_map(|input| match input
+	{
+		FnArg::Receiver(receiver) => Some(Ident::new("self", receiver.self_token.span)),
+		FnArg::Typed(typed) => match *typed.pat
+		{
+			Pat::Ident(ref pat) => Some(pat.ident.clone()),
+			_ => None,
+		},
+	}).collect();
 
-	(quote!
+	for (i, arg) in args.capture.iter().enumerate()
 	{
-		#[doc = #error_doc]
-		#[derive(Debug)]
-		#vis enum #error_ident
+		if !params.contains(arg)
 		{
-			#(#error_variants (#errors_one)),*
+			return Err(syn::Error::new_spanned(arg, format!("`{}` is not a parameter of this function", arg)));
 		}
 
-		#[automatically_derived]
-		impl core::fmt::Display for #error_ident
+		if args.capture[..i].contains(arg)
 		{
-			fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result
-			{
-				match self
-				{
-					#(Self::#error_variants_two(e) => write!(f, "{}", e)),*
-				}
-			}
+			return Err(syn::Error::new_spanned(arg, format!("`{}` is captured more than once", arg)));
 		}
+	}
+
+	// The body is run by a closure, whose return type cannot be an `impl Trait`.
+	if let (None, Some(token)) = (sig.asyncness, impl_trait(output.clone()))
+	{
+		return Err(syn::Error::new_spanned(token, "`capture` cannot be used on a function which returns an `impl Trait`"));
+	}
 
-		#(
-			#[automatically_derived]
-			impl std::convert::From<#errors_two> for #error_ident
+	// The body borrows the arguments, so that they are only formatted once it returns an `Error`.
+	let result = Ident::new("result", Span::mixed_site());
+	let names = &args.capture;
+	let body = match sig.asyncness
+	{
+		Some(_) => quote!(async #block.await),
+		None => quote!((|| -> #output #block)()),
+	};
+
+	Ok(quote!
+	{{
+		let #result: #output = #body;
+		#result.map_err(|mut e|
+		{
+			*e.captures_mut() = [#(std::format!("{:?}", #names)),*];
+			e
+		})
+	}})
+}
+
+/// The first `impl` of an `impl Trait` within the `tokens`, if any.
+fn impl_trait(tokens: TokenStream2) -> Option<Ident>
+{
+	tokens.into_iter().find_map(|token| match token
+	{
+		TokenTree::Group(group) => impl_trait(group.stream()),
+		TokenTree::Ident(ident) if ident == "impl" => Some(ident),
+		TokenTree::Ident(_) | TokenTree::Punct(_) | TokenTree::Literal(_) => None,
+	})
+}
+
+/// The `tokens` as they were written in the source, for use in diagnostics.
+pub(crate) fn written(tokens: impl ToTokens) -> String
+{
+	tokens.to_token_stream().to_string()
+		.replace(" :: ", "::")
+		.replace(":: ", "::")
+		.replace(" < ", "<")
+		.replace(" <", "<")
+		.replace("< ", "<")
+		.replace(" >", ">")
+		.replace(" ,", ",")
+		.replace("& ", "&")
+}
+
+#[cfg(test)]
+mod tests
+{
+	use
+	{
+		super::expand_errors,
+
+		proc_macro2::TokenStream as TokenStream2,
+		quote::quote,
+		syn::{Item, Lit, Meta},
+	};
+
+	/// The lines of the documentation of the function `read_int` when the `attrs` are given to it
+	/// alongside `#[errors(errors_doc, ...)]`.
+	fn errors_doc(attrs: TokenStream2) -> Vec<String>
+	{
+		let expanded = expand_errors(
+			quote!(errors_doc, std::io::Error, std::num::ParseIntError),
+			quote!(#attrs fn read_int(path: &str) -> i128 { Ok(std::fs::read_to_string(path)?.trim().parse()?) }),
+		);
+
+		let file: syn::File = syn::parse2(expanded).unwrap();
+		file.items.into_iter()
+			.find_map(|item| match item
 			{
-				fn from(e: #errors_two) -> Self
+				Item::Fn(function) if function.sig.ident == "read_int" => Some(function.attrs),
+				_ => None,
+			})
+			.unwrap()
+			.into_iter()
+			.filter_map(|a| match a.parse_meta()
+			{
+				Ok(Meta::NameValue(meta)) if meta.path.is_ident("doc") => match meta.lit
 				{
-					Self::#error_variants_three(e)
-				}
-			}
-		)*
+					Lit::Str(doc) => Some(doc.value()),
+					_ => None,
+				},
+				_ => None,
+			})
+			.collect()
+	}
+
+	#[test]
+	fn errors_doc_follows_the_docs()
+	{
+		assert_eq!(errors_doc(quote!(#[doc = " Read an integer."] #[inline])), [
+			" Read an integer.",
+			"",
+			"# Errors",
+			"",
+			" - [`ReadIntError::StdIo`] — propagated from [`std::io::Error`]",
+			" - [`ReadIntError::StdNumParseInt`] — propagated from [`std::num::ParseIntError`]",
+		]);
+	}
+
+	#[test]
+	fn errors_doc_without_docs()
+	{
+		assert_eq!(errors_doc(quote!()), [
+			"# Errors",
+			"",
+			" - [`ReadIntError::StdIo`] — propagated from [`std::io::Error`]",
+			" - [`ReadIntError::StdNumParseInt`] — propagated from [`std::num::ParseIntError`]",
+		]);
+	}
+
+	#[test]
+	fn expansion_is_deterministic()
+	{
+		let expand = || expand_errors(
+			quote!(std::io::Error, std::num::ParseIntError),
+			quote!(fn read_int(path: &str) -> i128 { Ok(std::fs::read_to_string(path)?.trim().parse()?) }),
+		).to_string();
 
-		#[automatically_derived]
-		impl std::error::Error for #error_ident {}
+		let expanded = expand();
+		assert_eq!(expanded, expand());
 
-		#(#attrs)* #vis #constness #asyncness #unsafety #abi #fn_token #ident #generics (#(#inputs),* #variadic)
-			-> std::result::Result<#output, #error_ident>
-		#where_clause
-		#block
-	}).into()
+		let position = |variant: &str| expanded.find(&format!("{} (", variant)).unwrap();
+		assert!(position("StdIo") < position("StdNumParseInt"));
+	}
 }
@@ -0,0 +1,649 @@
+use
+{
+	crate::{args::Args, entry, errors_doc, expand::{self, ErrorEnum}, generics, is_errors, ok_type, parse_fn, stacked, written},
+
+	heck::CamelCase,
+	proc_macro2::{Group, Span, Spacing, TokenStream as TokenStream2, TokenTree},
+	quote::{format_ident, quote, ToTokens},
+	syn::
+	{
+		ext::IdentExt, parse::Parser, spanned::Spanned, Attribute, Generics, Ident, ImplItem, ImplItemMethod, Item, ItemFn, ItemImpl,
+		ItemMod, ItemTrait, LitStr, Signature, TraitItem, Type, Visibility,
+	},
+};
+
+/// Where the function which the `#[errors]` macro rewrites was defined.
+#[derive(Clone, Copy)]
+pub(crate) enum Scope<'a>
+{
+	/// A free function.
+	Free,
+
+	/// A method in an `impl` block.
+	Impl(&'a ItemImpl),
+
+	/// A method in a trait.
+	Trait(&'a ItemTrait),
+}
+
+impl Scope<'_>
+{
+	/// The generic parameters of the `impl` block or trait, which its methods may refer to.
+	pub(crate) fn generics(&self) -> Option<&Generics>
+	{
+		match self
+		{
+			Self::Free => None,
+			Self::Impl(block) => Some(&block.generics),
+			Self::Trait(definition) => Some(&definition.generics),
+		}
+	}
+
+	/// How the function called `ident` is referred to in documentation, e.g. `Config::load`.
+	pub(crate) fn path(&self, ident: &Ident) -> String
+	{
+		match self
+		{
+			Self::Impl(block) => match type_name(&block.self_ty)
+			{
+				Some(ty) => format!("{}::{}", ty, ident),
+				None => ident.to_string(),
+			},
+			Self::Free => ident.to_string(),
+			Self::Trait(definition) => format!("{}::{}", definition.ident, ident),
+		}
+	}
+
+	/// The name of the items generated for the function called `ident`, which their kinds are
+	/// appended to (e.g. `StoreLoad` for the `StoreLoadError` of `Store::load`).
+	pub(crate) fn name(&self, ident: &Ident) -> String
+	{
+		format!("{}{}", self.prefix(), ident.unraw().to_string().to_camel_case())
+	}
+
+	/// What the names of the items generated for a function are prefixed with, so that they do not
+	/// collide with those of functions of the same name elsewhere (e.g. `Store` for `Store::load`).
+	pub(crate) fn prefix(&self) -> String
+	{
+		match self
+		{
+			Self::Free => String::new(),
+			Self::Impl(block) => type_name(&block.self_ty).map(|ty| ty.unraw().to_string()).unwrap_or_default(),
+			Self::Trait(definition) => definition.ident.unraw().to_string(),
+		}
+	}
+
+	/// The `tokens` with each `Self` replaced by the type which it refers to, since the items which
+	/// the macro generates are defined outside of the `impl` block.
+	///
+	/// The `Self` of a trait is not known, so it cannot be referred to.
+	pub(crate) fn resolve(&self, tokens: TokenStream2) -> syn::Result<TokenStream2>
+	{
+		match self
+		{
+			Self::Impl(block) => Ok(replace_self(tokens, &block.self_ty)),
+			Self::Free => Ok(tokens),
+			Self::Trait(_) => match find_self(tokens.clone())
+			{
+				Some(this) => Err(syn::Error::new(this.span(), TRAIT_SELF)),
+				None => Ok(tokens),
+			},
+		}
+	}
+
+	/// The visibility of the items generated for a function, if it is not the function's own (e.g.
+	/// the methods of a trait are as visible as the trait).
+	pub(crate) fn vis(&self) -> Option<&Visibility>
+	{
+		match self
+		{
+			Self::Free | Self::Impl(_) => None,
+			Self::Trait(definition) => Some(&definition.vis),
+		}
+	}
+}
+
+/// The message reported when `#[errors(...)]` is given arguments on a trait.
+const TRAIT_ARGS: &str = "the #[errors] macro cannot be given arguments on a trait; give them to each of its methods instead";
+
+/// The message reported when the `Error`s of a trait's method refer to `Self`.
+const TRAIT_SELF: &str = "the `Error`s of a trait's method cannot refer to `Self`, since the generated `Error` is defined outside of the trait";
+
+/// How a method of an `impl` block is rewritten.
+enum Method
+{
+	/// The method is left as it is.
+	Kept(Box<ImplItem>),
+
+	/// The method returns its own `Error`, described by the `Args` of its `#[errors(...)]`.
+	Own(ImplItemMethod, Box<Args>),
+
+	/// The method returns the `Error` of the `impl` block.
+	Shared(ImplItemMethod),
+}
+
+/// Rewrite each method of the `block` which has an `#[errors(...)]` attribute, and generate its
+/// `Error` before the `block`.
+///
+/// If the `block` itself is given `Error`s (by the `attr`), one `Error` is generated for the whole
+/// `block`, which each other method returns unless it is given `skip` (or already returns it).
+pub(crate) fn expand_impl(mut block: ItemImpl, attr: TokenStream2) -> syn::Result<TokenStream2>
+{
+	let mut shared = match attr.is_empty()
+	{
+		true => None,
+		false => Some(syn::parse2::<Args>(Scope::Impl(&block).resolve(attr)?)?),
+	};
+
+	let ty = type_name(&block.self_ty).map(Ident::to_string);
+	let names = match shared
+	{
+		Some(ref args) =>
+		{
+			Shared::check(args, "an `impl` block", "method")?;
+			match (ty.as_ref(), args.name.is_some())
+			{
+				(Some(ty), _) => Shared::names(args, ty),
+				(None, true) => Shared::names(args, &written(&block.self_ty)),
+				(None, false) => return Err(syn::Error::new_spanned(&block.self_ty,
+					"the name of the `Error` for this `impl` block cannot be derived from its type; give it a `name` instead",
+				)),
+			}
+		},
+		None => Vec::new(),
+	};
+
+	// The trait can only name the `Error`s which are given to it by the `impl`.
+	let nameable = trait_arguments(&block);
+	let mut methods = Vec::with_capacity(block.items.len());
+	for item in std::mem::take(&mut block.items)
+	{
+		methods.push(match item
+		{
+			ImplItem::Method(mut method) if method.attrs.iter().any(is_errors) =>
+			{
+				let args = method_args(Scope::Impl(&block), &mut method.attrs)?;
+				match args.skip
+				{
+					Some(_) => Method::Kept(Box::new(ImplItem::Method(method))),
+					None => Method::Own(method, Box::new(args)),
+				}
+			},
+			ImplItem::Method(method) if Shared::returned_by(&names, &method.sig) => Method::Shared(method),
+			other => Method::Kept(Box::new(other)),
+		});
+	}
+
+	let vis = Shared::vis(methods.iter().filter_map(|m| match m
+	{
+		Method::Shared(method) => Some(&method.vis),
+		_ => None,
+	}));
+
+	let shared = match shared.as_mut()
+	{
+		Some(args) =>
+		{
+			let ty = ty.unwrap_or_else(|| written(&block.self_ty));
+			let generics = generics::used(&mut args.entries, &block.generics);
+			let link = format!("[`{}`]", ty);
+			Some(Shared::new(args, names, "methods", ty, link, generics, vis)?)
+		},
+		None => None,
+	};
+
+	let mut own = Vec::new();
+	for method in methods
+	{
+		block.items.push(match method
+		{
+			Method::Kept(item) => *item,
+			Method::Own(method, args) =>
+			{
+				if args.name.is_none() && args.share.is_none() && args.existing.is_none() && !args.declare_only
+				{
+					let error = format_ident!("{}Error", Scope::Impl(&block).name(&method.sig.ident));
+					check_trait(nameable.as_ref(), &method.sig, &error)?;
+				}
+
+				let defaultness = method.defaultness;
+				let (error, rewritten) = parse_fn(method.attrs, method.vis, method.sig, Some(method.block), *args, Scope::Impl(&block))?;
+				own.push(error);
+
+				let mut rewritten: ImplItemMethod = syn::parse2(rewritten)?;
+				rewritten.defaultness = defaultness;
+				ImplItem::Method(rewritten)
+			},
+			Method::Shared(mut method) =>
+			{
+				if let Some(ref shared) = shared
+				{
+					if shared.args.name.is_none() && shared.args.existing.is_none()
+					{
+						check_trait(nameable.as_ref(), &method.sig, &shared.names[0])?;
+					}
+
+					shared.rewrite(&mut method.attrs, &mut method.sig)?;
+				}
+
+				ImplItem::Method(method)
+			},
+		});
+	}
+
+	let items = shared.map(|s| s.items);
+	Ok(quote!
+	{
+		#items
+		#(#own)*
+		#block
+	})
+}
+
+/// Check that the trait of an `impl` can name the `error` which the method with the `sig` is
+/// rewritten to return, through the `nameable` tokens of the `impl` (see [`trait_arguments`]).
+///
+/// An `Error` which was given its `name` may be named by the trait itself, so only those whose names
+/// are derived are checked.
+fn check_trait(nameable: Option<&TokenStream2>, sig: &Signature, error: &Ident) -> syn::Result<()>
+{
+	match nameable
+	{
+		Some(tokens) if generics::mentions(tokens.clone(), &[error]).is_empty() => Err(syn::Error::new_spanned(sig, format!(
+			"this method is rewritten to return `{}`, which its trait cannot name; the trait should return an associated type, which this `impl` gives the `Error` (e.g. `type Error = {};`)",
+			error, error,
+		))),
+		_ => Ok(()),
+	}
+}
+
+/// The path of the trait which the `block` implements, and the associated types which it gives the
+/// trait, if it is an `impl` of a trait.
+fn trait_arguments(block: &ItemImpl) -> Option<TokenStream2>
+{
+	let (_, path, _) = block.trait_.as_ref()?;
+	let mut tokens = path.to_token_stream();
+	tokens.extend(block.items.iter().filter_map(|item| match item
+	{
+		ImplItem::Type(ty) => Some(ty.ty.to_token_stream()),
+		_ => None,
+	}));
+
+	Some(tokens)
+}
+
+/// Rewrite each function of the `module` to return one `Error`, described by the `attr`, which is
+/// generated at the start of the `module`.
+///
+/// Functions which are given `skip` (or which already return the `Error`) are left as they are, as
+/// are those which have their own `#[errors(...)]`.
+pub(crate) fn expand_mod(mut module: ItemMod, attr: TokenStream2) -> syn::Result<TokenStream2>
+{
+	let mut args: Args = syn::parse2(attr)?;
+	Shared::check(&args, "a module", "function")?;
+
+	let ty = module.ident.unraw().to_string().to_camel_case();
+	let names = Shared::names(&args, &ty);
+	let content = match module.content
+	{
+		Some((_, ref mut content)) => content,
+		None => return Err(syn::Error::new_spanned(&module, "the #[errors] macro cannot see the functions of a module which is defined in another file")),
+	};
+
+	let mut functions = Vec::new();
+	collect(content, args.nested, &names, &mut functions);
+
+	let vis = Shared::vis(functions.iter().map(|f| &f.vis));
+	let link = format!("[`{}`](self)", module.ident);
+	let shared = Shared::new(&mut args, names, "functions", module.ident.to_string(), link, Generics::default(), vis)?;
+	rewrite(content, shared.args.nested, &shared)?;
+
+	let items = syn::parse2::<syn::File>(shared.items.clone())?.items;
+	content.splice(0..0, items);
+	Ok(module.into_token_stream())
+}
+
+/// Push each function in the `items` which should return the `Error` with the `names` into the
+/// `functions` (including those of `nested` modules, if they are).
+fn collect<'a>(items: &'a [Item], nested: bool, names: &[Ident], functions: &mut Vec<&'a ItemFn>)
+{
+	for item in items
+	{
+		match item
+		{
+			// Functions with their own `#[errors(...)]` are rewritten by it.
+			Item::Fn(function) if !function.attrs.iter().any(is_errors) && Shared::returned_by(names, &function.sig) => functions.push(function),
+			Item::Mod(ItemMod {content: Some((_, content)), ..}) if nested => collect(content, nested, names, functions),
+			_ => (),
+		}
+	}
+}
+
+/// Rewrite each function in the `items` to return the `shared` `Error` (including those of `nested`
+/// modules, which import it from their parent).
+fn rewrite(items: &mut [Item], nested: bool, shared: &Shared) -> syn::Result<()>
+{
+	for item in items.iter_mut()
+	{
+		match item
+		{
+			Item::Fn(function) if function.attrs.iter().any(is_errors) =>
+			{
+				let mut attrs = function.attrs.clone();
+				let args: Args = syn::parse2(stacked(TokenStream2::new(), &mut attrs))?;
+				if args.skip.is_some()
+				{
+					function.attrs = attrs;
+				}
+			},
+			Item::Fn(function) if Shared::returned_by(&shared.names, &function.sig) => shared.rewrite(&mut function.attrs, &mut function.sig)?,
+			Item::Mod(ItemMod {content: Some((_, content)), ..}) if nested =>
+			{
+				rewrite(content, nested, shared)?;
+
+				// Only the generated items can be imported.
+				let generated = match (shared.error.is_some(), shared.args.result_alias.is_some())
+				{
+					(false, _) => 0,
+					(true, false) => 1,
+					(true, true) => 2,
+				};
+
+				let names = &shared.names[..generated];
+				if !names.is_empty()
+				{
+					content.insert(0, syn::parse_quote!(#[allow(unused_imports)] use super::{#(#names),*};));
+				}
+			},
+			_ => (),
+		}
+	}
+
+	Ok(())
+}
+
+/// The `Error` which is shared by each function in an `impl` block or module that is given
+/// `Error`s.
+struct Shared<'a>
+{
+	/// The arguments given to the `impl` block or module.
+	args: &'a Args,
+
+	/// The generated `Error`, unless an existing one was given by the `use` option.
+	error: Option<ErrorEnum<'a>>,
+
+	/// The generated `Error` and its `Result` alias.
+	items: TokenStream2,
+
+	/// The names of the `Error` and its `Result` alias.
+	names: Vec<Ident>,
+
+	/// The `Error` as a type.
+	ty: TokenStream2,
+}
+
+impl<'a> Shared<'a>
+{
+	/// Check that the `args` given to the `owner` (e.g. "an `impl` block") of several `function`s
+	/// (e.g. "method") can be shared by them.
+	fn check(args: &Args, owner: &str, function: &str) -> syn::Result<()>
+	{
+		if let Some(arg) = args.capture.first()
+		{
+			return Err(syn::Error::new_spanned(arg, format!("`capture` cannot be given to {}, since the arguments of each {} differ", owner, function)));
+		}
+
+		if let Some(ref share) = args.share
+		{
+			return Err(syn::Error::new_spanned(share, format!("`share` cannot be given to {}, since its `Error` is already shared by each {}", owner, function)));
+		}
+
+		if args.declare_only
+		{
+			return Err(syn::Error::new(Span::call_site(), format!("`declare_only` cannot be given to {}; give it to each {} instead", owner, function)));
+		}
+
+		if let Some(ref defer) = args.defer
+		{
+			return Err(syn::Error::new_spanned(defer, format!("`defer` cannot be given to {}; give it to each free function instead", owner)));
+		}
+
+		if args.infer
+		{
+			return Err(syn::Error::new(Span::call_site(), format!("`infer` cannot be given to {}; give it to each {} instead", owner, function)));
+		}
+
+		if args.strict
+		{
+			return Err(syn::Error::new(Span::call_site(), format!("`strict` cannot be given to {}; give it to each {} instead", owner, function)));
+		}
+
+		if args.passthrough
+		{
+			return Err(syn::Error::new(Span::call_site(), format!("`passthrough` cannot be given to {}, since each {} returns a different type", owner, function)));
+		}
+
+		if args.wrap.is_some()
+		{
+			return Err(syn::Error::new(Span::call_site(), format!("`wrap` cannot be given to {}, since each {} returns a different type", owner, function)));
+		}
+
+		Ok(())
+	}
+
+	/// The names of the `Error` which the `args` describe for the `owner` (e.g. `Config`), and of
+	/// its `Result` alias.
+	fn names(args: &Args, owner: &str) -> Vec<Ident>
+	{
+		vec![
+			args.name.clone().unwrap_or_else(|| format_ident!("{}Error", owner)),
+			args.result_alias.clone().flatten().unwrap_or_else(|| format_ident!("{}Result", owner)),
+		]
+	}
+
+	/// Whether the function with the `sig` should be rewritten to return the `Error` with the
+	/// `names` (i.e. it does not already return it, or its `Result` alias).
+	fn returned_by(names: &[Ident], sig: &Signature) -> bool
+	{
+		!names.is_empty() && generics::mentions(sig.output.to_token_stream(), &names.iter().collect::<Vec<_>>()).is_empty()
+	}
+
+	/// The visibility of the `Error`, which is that of the most visible function which returns it.
+	fn vis<'v>(functions: impl Iterator<Item = &'v Visibility>) -> Visibility
+	{
+		functions.max_by_key(|v| visibility(v)).cloned().unwrap_or(Visibility::Inherited)
+	}
+
+	/// Generate the `Error` described by the `args`, with the `names`, which is returned by the
+	/// `functions` (e.g. "methods") of the `owner` (which is documented with the `link`).
+	fn new(
+		args: &'a mut Args,
+		names: Vec<Ident>,
+		functions: &str,
+		owner: String,
+		link: String,
+		generics: Generics,
+		vis: Visibility,
+	) -> syn::Result<Self>
+	{
+		if let Some(ref existing) = args.existing
+		{
+			let ty = existing.to_token_stream();
+			return Ok(Self {args, error: None, items: TokenStream2::new(), names, ty});
+		}
+
+		if args.doc.is_none()
+		{
+			let doc = format!("The [error](std::error::Error) returned by the {} of {}.", functions, link);
+			args.doc = Some(LitStr::new(&doc, Span::call_site()));
+		}
+
+		entry::sort(args)?;
+		let args = &*args;
+		let error = ErrorEnum::new(args, owner, generics, names[0].clone(), vis)?;
+		let ty = error.ty();
+		let result_alias = args.result_alias.as_ref().map(|_|
+		{
+			let alias = &names[1];
+			let doc = format!(
+				"The [`Result`](std::result::Result) of the {} of {}, which may fail with a [`{}`].",
+				functions, link, error.ident,
+			);
+
+			let lifetimes = error.generics.lifetimes().map(|l| &l.lifetime);
+			let params = error.generics.type_params().map(|p| &p.ident);
+			let t = generics::fresh("T", &error.generics);
+			let vis = &error.vis;
+			quote!
+			{
+				#[doc = #doc]
+				#vis type #alias<#(#lifetimes,)* #(#params,)* #t> = std::result::Result<#t, #ty>;
+			}
+		});
+
+		let mut items = error.expand();
+		items.extend(result_alias);
+		if args.no_std
+		{
+			items = expand::without_std(items);
+		}
+
+		let items = error.declare(items);
+
+		Ok(Self {args, error: Some(error), items, names, ty})
+	}
+
+	/// Rewrite the function with the `attrs` and `sig` to return this `Error`.
+	fn rewrite(&self, attrs: &mut Vec<Attribute>, sig: &mut Signature) -> syn::Result<()>
+	{
+		if let Some(ref error) = self.error
+		{
+			if self.args.errors_doc
+			{
+				let doc = errors_doc(attrs, error, &sig.ident);
+				attrs.extend(Attribute::parse_outer.parse2(doc)?);
+			}
+		}
+
+		let output = ok_type(sig);
+		let ty = &self.ty;
+		sig.output = syn::parse_quote_spanned!(output.span()=> -> core::result::Result<#output, #ty>);
+		Ok(())
+	}
+}
+
+/// Rewrite each method of the `definition` which has an `#[errors(...)]` attribute, and generate
+/// its `Error` before the `definition`.
+pub(crate) fn expand_trait(mut definition: ItemTrait, attr: TokenStream2) -> syn::Result<TokenStream2>
+{
+	if !attr.is_empty()
+	{
+		return Err(syn::Error::new_spanned(attr, TRAIT_ARGS));
+	}
+
+	let mut items = Vec::new();
+	let mut methods = Vec::with_capacity(definition.items.len());
+	for item in std::mem::take(&mut definition.items)
+	{
+		let mut method = match item
+		{
+			TraitItem::Method(method) if method.attrs.iter().any(is_errors) => method,
+			other =>
+			{
+				methods.push(other);
+				continue;
+			},
+		};
+
+		let scope = Scope::Trait(&definition);
+		let args = method_args(scope, &mut method.attrs)?;
+		let (error, method) = parse_fn(method.attrs, Visibility::Inherited, method.sig, method.default, args, scope)?;
+		items.push(error);
+		methods.push(TraitItem::Verbatim(method));
+	}
+
+	definition.items = methods;
+	Ok(quote!
+	{
+		#(#items)*
+		#definition
+	})
+}
+
+/// The arguments of the `#[errors(...)]` attributes in the `attrs` of a method in the `scope`
+/// (which are removed).
+fn method_args(scope: Scope, attrs: &mut Vec<Attribute>) -> syn::Result<Args>
+{
+	let args: Args = syn::parse2(scope.resolve(stacked(TokenStream2::new(), attrs))?)?;
+	if let Some(ref set) = args.set
+	{
+		return Err(syn::Error::new_spanned(set, "a set defined by `error_set!` can only be named by the #[errors] macro on free functions"));
+	}
+
+	Ok(args)
+}
+
+/// The first `Self` in the `tokens`, if any.
+fn find_self(tokens: TokenStream2) -> Option<Ident>
+{
+	tokens.into_iter().find_map(|tt| match tt
+	{
+		TokenTree::Group(group) => find_self(group.stream()),
+		TokenTree::Ident(ident) if ident == "Self" => Some(ident),
+		_ => None,
+	})
+}
+
+/// How visible the `vis` is, relative to other visibilities.
+fn visibility(vis: &Visibility) -> u8
+{
+	match vis
+	{
+		Visibility::Public(_) => 3,
+		Visibility::Crate(_) => 2,
+		Visibility::Restricted(restricted) if restricted.path.is_ident("crate") => 2,
+		Visibility::Restricted(_) => 1,
+		Visibility::Inherited => 0,
+	}
+}
+
+/// The name of the `ty` without its generic arguments, e.g. `Store` for `Store<T>`.
+fn type_name(ty: &Type) -> Option<&Ident>
+{
+	match ty
+	{
+		Type::Group(group) => type_name(&group.elem),
+		Type::Paren(paren) => type_name(&paren.elem),
+		Type::Path(path) if path.qself.is_none() => path.path.segments.last().map(|s| &s.ident),
+		Type::Reference(reference) => type_name(&reference.elem),
+		_ => None,
+	}
+}
+
+/// The `tokens` with each `Self` replaced by the `ty` (in angle brackets when it begins a path,
+/// e.g. `<Store<T>>::Err`).
+fn replace_self(tokens: TokenStream2, ty: &Type) -> TokenStream2
+{
+	let mut replaced = TokenStream2::new();
+	let mut tokens = tokens.into_iter().peekable();
+	while let Some(tt) = tokens.next()
+	{
+		match tt
+		{
+			TokenTree::Group(group) =>
+			{
+				let mut inner = Group::new(group.delimiter(), replace_self(group.stream(), ty));
+				inner.set_span(group.span());
+				replaced.extend(Some(TokenTree::Group(inner)));
+			},
+			TokenTree::Ident(ref ident) if ident == "Self" => match tokens.peek()
+			{
+				Some(TokenTree::Punct(p)) if p.as_char() == ':' && p.spacing() == Spacing::Joint => replaced.extend(quote!(<#ty>)),
+				_ => ty.to_tokens(&mut replaced),
+			},
+			other => replaced.extend(Some(other)),
+		}
+	}
+
+	replaced
+}
@@ -0,0 +1,114 @@
+use
+{
+	crate::entry::Entry,
+
+	proc_macro2::{TokenStream as TokenStream2, TokenTree},
+	quote::quote,
+	syn::{parse::{Parse, ParseStream}, punctuated::Punctuated, Ident, Path, Token},
+};
+
+/// A named set of `Error`s defined by the `error_set!` macro, e.g.
+/// `IoAndParse = [std::io::Error, std::num::ParseIntError]`.
+pub(crate) struct ErrorSet
+{
+	/// The `Error`s in the set, as they were written.
+	entries: TokenStream2,
+
+	/// The name of the set.
+	name: Ident,
+}
+
+impl ErrorSet
+{
+	/// The `macro_rules!` which the `#[errors]` macro calls back into when it is given the `set`
+	/// option, which passes it the `Error`s of this set.
+	pub(crate) fn expand(&self) -> TokenStream2
+	{
+		let Self {entries, name} = self;
+		quote!
+		{
+			#[allow(unused_macros)]
+			macro_rules! #name
+			{
+				(($($args:tt)*) $($item:tt)*) =>
+				{
+					#[::erro_rs::errors(set = [#entries], $($args)*)]
+					$($item)*
+				};
+			}
+
+			#[allow(unused_imports)]
+			pub(crate) use #name;
+		}
+	}
+}
+
+impl Parse for ErrorSet
+{
+	fn parse(input: ParseStream) -> syn::Result<Self>
+	{
+		let name = input.parse()?;
+		input.parse::<Token![=]>()?;
+
+		let content;
+		syn::bracketed!(content in input);
+		let entries: TokenStream2 = content.parse()?;
+
+		// Checked here, so that mistakes are reported where the set is defined.
+		syn::parse::Parser::parse2(Punctuated::<Entry, Token![,]>::parse_terminated, entries.clone())?;
+
+		input.parse::<Option<Token![;]>>()?;
+		Ok(Self {entries, name})
+	}
+}
+
+/// The call of the `macro_rules!` for the set at `path`, which passes it the `item` and the
+/// arguments of the `#[errors]` macro (without the `set = ...` which named it).
+pub(crate) fn call(path: &Path, args: TokenStream2, item: TokenStream2) -> TokenStream2
+{
+	let args = without_set(args);
+	quote!(#path! { (#args) #item })
+}
+
+/// The `args` without the first `set = ...` which names a set (rather than listing its `Error`s).
+fn without_set(args: TokenStream2) -> TokenStream2
+{
+	let mut removed = false;
+
+	// Generic arguments may contain commas too, e.g. `<T as TryFrom<A, B>>::Error`.
+	let mut depth = 0usize;
+	let mut kept = Vec::new();
+	let mut arg = Vec::new();
+	for tt in args.into_iter().map(Some).chain(std::iter::once(None))
+	{
+		match tt
+		{
+			Some(TokenTree::Punct(ref p)) if p.as_char() == '<' => depth += 1,
+			Some(TokenTree::Punct(ref p)) if p.as_char() == '>' => depth = depth.saturating_sub(1),
+			Some(TokenTree::Punct(ref p)) if p.as_char() != ',' || depth > 0 => (),
+			Some(TokenTree::Punct(_)) | None =>
+			{
+				let names_set = matches!(
+					arg.as_slice(),
+					[TokenTree::Ident(set), TokenTree::Punct(eq), rest @ ..]
+						if set == "set" && eq.as_char() == '=' && !matches!(rest, [TokenTree::Group(_)])
+				);
+
+				match names_set && !removed
+				{
+					true => removed = true,
+					false if !arg.is_empty() => kept.push(arg.drain(..).collect::<TokenStream2>()),
+					false => (),
+				}
+
+				arg.clear();
+				continue;
+			},
+			_ => (),
+		}
+
+		arg.extend(tt);
+	}
+
+	quote!(#(#kept),*)
+}
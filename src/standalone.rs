@@ -0,0 +1,104 @@
+use
+{
+	crate::{args::Args, entry, expand::{self, ErrorEnum}},
+
+	proc_macro2::{Span, TokenStream as TokenStream2},
+	quote::{format_ident, quote},
+	syn::{parse::{Parse, ParseStream}, Generics, Ident, LitStr, Token, Visibility},
+};
+
+/// An `Error` declared by the `errors_enum!` macro, e.g.
+/// `pub ConfigError: std::io::Error, std::num::ParseIntError = "Parse"`.
+pub(crate) struct Standalone
+{
+	/// The options and entries of the `Error`, as the `#[errors]` macro would be given them.
+	args: Args,
+
+	/// The name of the `Error`.
+	ident: Ident,
+
+	/// The visibility of the `Error`.
+	vis: Visibility,
+}
+
+impl Standalone
+{
+	/// The `Error`, and the items which accompany it.
+	pub(crate) fn expand(mut self) -> syn::Result<TokenStream2>
+	{
+		// Options which describe how a function is rewritten have nothing to describe.
+		let args = &self.args;
+		let rejected = [
+			(!args.capture.is_empty(), "capture"),
+			(args.declare_only, "declare_only"),
+			(args.defer.is_some(), "defer"),
+			(args.errors_doc, "errors_doc"),
+			(args.existing.is_some() && args.share.is_none(), "use"),
+			(args.infer, "infer"),
+			(args.name.is_some(), "name"),
+			(args.nested, "nested"),
+			(args.passthrough, "passthrough"),
+			(args.set.is_some(), "set"),
+			(args.share.is_some(), "share"),
+			(args.skip.is_some(), "skip"),
+			(args.strict, "strict"),
+			(args.wrap.is_some(), "wrap"),
+		];
+
+		if let Some(option) = rejected.iter().find_map(|&(given, option)| given.then_some(option))
+		{
+			return Err(syn::Error::new(Span::call_site(), format!(
+				"`{}` cannot be given to `errors_enum!`, since the `Error` is not returned by a function it rewrites", option,
+			)));
+		}
+
+		if self.args.doc.is_none()
+		{
+			let doc = format!("The [error](std::error::Error) which may be any of the `{}`'s variants.", self.ident);
+			self.args.doc = Some(LitStr::new(&doc, Span::call_site()));
+		}
+
+		entry::sort(&mut self.args)?;
+		let Self {args, ident, vis} = &self;
+		let error = ErrorEnum::new(args, ident.to_string(), Generics::default(), ident.clone(), vis.clone())?;
+		let result_alias = args.result_alias.as_ref().map(|alias|
+		{
+			let name = ident.to_string();
+			let alias = alias.clone().unwrap_or_else(|| format_ident!("{}Result", name.strip_suffix("Error").unwrap_or(&name)));
+			let doc = format!("A [`Result`](std::result::Result) which may fail with a [`{}`].", ident);
+			let vis = &error.vis;
+			quote!
+			{
+				#[doc = #doc]
+				#vis type #alias<T> = std::result::Result<T, #ident>;
+			}
+		});
+
+		let mut items = error.expand();
+		items.extend(result_alias);
+		if args.no_std
+		{
+			items = expand::without_std(items);
+		}
+
+		let items = error.declare(items);
+		if args.debug
+		{
+			eprintln!("`errors_enum!` expanded to:\n{}\n", items);
+		}
+
+		Ok(items)
+	}
+}
+
+impl Parse for Standalone
+{
+	fn parse(input: ParseStream) -> syn::Result<Self>
+	{
+		let vis = input.parse()?;
+		let ident = input.parse()?;
+		input.parse::<Token![:]>()?;
+		let args = input.parse()?;
+		Ok(Self {args, ident, vis})
+	}
+}
@@ -0,0 +1,91 @@
+use
+{
+	syn::{GenericArgument, LitStr, PathArguments, Type, TypeParamBound},
+};
+
+/// What the `Error`s are returned by, when it is not the function itself.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum Wrap
+{
+	/// The `Output` of the `Future` which the function returns, e.g. `T` in
+	/// `Pin<Box<dyn Future<Output = T>>>`.
+	FutureOutput,
+
+	/// The `Item` of the iterator (or `Stream`, etc.) which the function returns, e.g. `T` in
+	/// `impl Iterator<Item = T>`.
+	Item,
+}
+
+impl Wrap
+{
+	/// Parse the value of the `wrap` option.
+	pub(crate) fn parse(lit: &LitStr) -> syn::Result<Self>
+	{
+		match lit.value().as_str()
+		{
+			"future_output" => Ok(Self::FutureOutput),
+			"item" => Ok(Self::Item),
+			_ => Err(syn::Error::new_spanned(lit, "expected `wrap = \"future_output\"` or `wrap = \"item\"`")),
+		}
+	}
+
+	/// The type in the `ty` which should return the `Error`s, if there is one.
+	pub(crate) fn find(self, ty: &mut Type) -> Option<&mut Type>
+	{
+		match self
+		{
+			Self::FutureOutput => binding(ty, Some("Future"), "Output"),
+			Self::Item => binding(ty, None, "Item"),
+		}
+	}
+
+	/// The message reported when the return type of a function has nothing to wrap.
+	pub(crate) fn missing(self) -> &'static str
+	{
+		match self
+		{
+			Self::FutureOutput => "`wrap = \"future_output\"` requires the function to return a `Future<Output = ...>` (e.g. `Pin<Box<dyn Future<Output = T>>>` or `impl Future<Output = T>`)",
+			Self::Item => "`wrap = \"item\"` requires the function to return a trait with an `Item = ...` binding (e.g. `impl Iterator<Item = T>` or `Box<dyn Stream<Item = T>>`)",
+		}
+	}
+}
+
+/// The type bound to `name` (e.g. `Output`) by the first bound on a trait (e.g. `Future`, or any
+/// trait if there is no `trait_name`) within the `ty`, looking through the generic arguments of
+/// paths like `Pin<Box<...>>`.
+fn binding<'t>(ty: &'t mut Type, trait_name: Option<&str>, name: &str) -> Option<&'t mut Type>
+{
+	let bounds = match ty
+	{
+		Type::Group(group) => return binding(&mut group.elem, trait_name, name),
+		Type::ImplTrait(impl_trait) => &mut impl_trait.bounds,
+		Type::Paren(paren) => return binding(&mut paren.elem, trait_name, name),
+		Type::Path(path) => return path.path.segments.iter_mut().find_map(|segment| match segment.arguments
+		{
+			PathArguments::AngleBracketed(ref mut args) => args.args.iter_mut().find_map(|arg| match arg
+			{
+				GenericArgument::Type(ty) => binding(ty, trait_name, name),
+				_ => None,
+			}),
+			_ => None,
+		}),
+		Type::TraitObject(object) => &mut object.bounds,
+		_ => return None,
+	};
+
+	bounds.iter_mut().find_map(|bound| match bound
+	{
+		TypeParamBound::Trait(bound) => bound.path.segments.last_mut()
+			.filter(|segment| trait_name.is_none_or(|t| segment.ident == t))
+			.and_then(|segment| match segment.arguments
+			{
+				PathArguments::AngleBracketed(ref mut args) => args.args.iter_mut().find_map(|arg| match arg
+				{
+					GenericArgument::Binding(binding) if binding.ident == name => Some(&mut binding.ty),
+					_ => None,
+				}),
+				_ => None,
+			}),
+		_ => None,
+	})
+}
@@ -0,0 +1,7 @@
+use erro_rs::errors;
+
+#[errors(non_exhaustive, std::io::Error, std::num::ParseIntError)]
+pub fn read_int(path: &str) -> i128
+{
+	Ok(std::fs::read_to_string(path)?.trim().parse()?)
+}
@@ -0,0 +1,12 @@
+use erro_rs_fixture::{read_int, ReadIntError};
+
+#[test]
+fn wildcard()
+{
+	match read_int("/this/path/does/not/exist")
+	{
+		Err(ReadIntError::StdIo(_)) => (),
+		Err(ReadIntError::StdNumParseInt(_)) | Ok(_) => unreachable!(),
+		Err(_) => unreachable!(),
+	}
+}
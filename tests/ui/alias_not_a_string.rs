@@ -0,0 +1,9 @@
+use erro_rs::errors;
+
+#[errors(std::io::Error = 3)]
+fn foo()
+{
+	Ok(())
+}
+
+fn main() {}
@@ -0,0 +1,9 @@
+use erro_rs::errors;
+
+#[errors("mpsc::SendError<u8>", "mpsc::SendError<u16>")]
+fn foo()
+{
+	Ok(())
+}
+
+fn main() {}
@@ -0,0 +1,9 @@
+use erro_rs::errors;
+
+#[errors(helpers, std::io::Error = "IOError", std::fmt::Error = "IoError")]
+fn foo()
+{
+	Ok(())
+}
+
+fn main() {}
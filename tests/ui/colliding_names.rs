@@ -0,0 +1,15 @@
+use erro_rs::errors;
+
+mod std_io
+{
+	pub type Error = std::io::Error;
+}
+
+// Both are named `StdIo`
+#[errors(std::io::Error, std_io::Error)]
+fn foo()
+{
+	Ok(())
+}
+
+fn main() {}
@@ -0,0 +1,9 @@
+use erro_rs::errors;
+
+#[errors(crate::Error)]
+fn foo()
+{
+	Ok(())
+}
+
+fn main() {}
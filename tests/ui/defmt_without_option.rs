@@ -0,0 +1,10 @@
+use erro_rs::errors;
+
+// the `defmt` entry option requires the `defmt` option
+#[errors(std::io::Error(defmt))]
+fn foo()
+{
+	Ok(())
+}
+
+fn main() {}
@@ -0,0 +1,23 @@
+use erro_rs::errors;
+
+#[derive(Debug)]
+struct LocalError(std::rc::Rc<()>);
+
+impl std::fmt::Display for LocalError
+{
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result
+	{
+		f.write_str("local")
+	}
+}
+
+impl std::error::Error for LocalError {}
+
+// `LocalError` is not `Send`, so the `Error` cannot be boxed
+#[errors(dyn_impls, LocalError)]
+fn foo()
+{
+	Ok(())
+}
+
+fn main() {}
@@ -0,0 +1,9 @@
+use erro_rs::errors;
+
+#[errors(enum_attrs(doc(hidden)), std::io::Error)]
+fn foo()
+{
+	Ok(())
+}
+
+fn main() {}
@@ -0,0 +1,9 @@
+use erro_rs::errors;
+
+#[errors(std::io::Error(boxed = true))]
+fn foo()
+{
+	Ok(())
+}
+
+fn main() {}
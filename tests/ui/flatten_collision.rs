@@ -0,0 +1,15 @@
+use erro_rs::errors;
+
+#[errors(std::io::Error)]
+fn read() -> String
+{
+	Ok(std::fs::read_to_string("foo")?)
+}
+
+#[errors(flatten(ReadError {StdIo: std::io::Error}), std::fmt::Error = "StdIo")]
+fn foo()
+{
+	Ok(())
+}
+
+fn main() {}
@@ -0,0 +1,10 @@
+use erro_rs::errors;
+
+// `help` requires `diagnostic`
+#[errors(std::io::Error(help = "check the path"))]
+fn foo()
+{
+	Ok(())
+}
+
+fn main() {}
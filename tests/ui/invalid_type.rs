@@ -0,0 +1,9 @@
+use erro_rs::errors;
+
+#[errors("std::sync::mpsc::SendError<")]
+fn foo()
+{
+	Ok(())
+}
+
+fn main() {}
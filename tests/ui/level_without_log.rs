@@ -0,0 +1,10 @@
+use erro_rs::errors;
+
+// `level` requires the `log` or `tracing` option
+#[errors(std::io::Error(level = "error"))]
+fn foo()
+{
+	Ok(())
+}
+
+fn main() {}
@@ -0,0 +1,9 @@
+use erro_rs::errors;
+
+#[errors(std::io::Error, 5)]
+fn foo()
+{
+	Ok(())
+}
+
+fn main() {}
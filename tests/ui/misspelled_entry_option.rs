@@ -0,0 +1,9 @@
+use erro_rs::errors;
+
+#[errors(std::io::Error(boxxed))]
+fn foo()
+{
+	Ok(())
+}
+
+fn main() {}
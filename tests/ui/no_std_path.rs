@@ -0,0 +1,9 @@
+use erro_rs::errors;
+
+#[errors(no_std, std::io::Error)]
+fn foo()
+{
+	Ok(())
+}
+
+fn main() {}
@@ -0,0 +1,11 @@
+use erro_rs_fixture::{read_int, ReadIntError};
+
+fn main()
+{
+	match read_int("/this/path/does/not/exist")
+	{
+		Ok(_) => (),
+		Err(ReadIntError::StdIo(_)) => (),
+		Err(ReadIntError::StdNumParseInt(_)) => (),
+	}
+}
@@ -0,0 +1,6 @@
+use erro_rs::errors;
+
+#[errors(std::io::Error)]
+struct Foo;
+
+fn main() {}
@@ -0,0 +1,9 @@
+use erro_rs::errors;
+
+#[errors(std::io::ErrorKind)]
+fn foo()
+{
+	Ok(())
+}
+
+fn main() {}
@@ -0,0 +1,9 @@
+use erro_rs::errors;
+
+#[errors(anyhow::Error)]
+fn foo()
+{
+	Ok(())
+}
+
+fn main() {}
@@ -0,0 +1,9 @@
+use erro_rs::errors;
+
+#[errors(<i32 as FromStr>::Err)]
+fn foo()
+{
+	Ok(())
+}
+
+fn main() {}
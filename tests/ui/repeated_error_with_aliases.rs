@@ -0,0 +1,9 @@
+use erro_rs::errors;
+
+#[errors(std::io::Error = "ReadFailed", std::io::Error = "WriteFailed")]
+fn copy(from: &str, to: &str)
+{
+	Ok(())
+}
+
+fn main() {}
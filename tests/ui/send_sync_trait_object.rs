@@ -0,0 +1,9 @@
+use erro_rs::errors;
+
+#[errors(send_sync, "Box<dyn std::error::Error>" = "Boxed")]
+fn foo()
+{
+	Ok(())
+}
+
+fn main() {}
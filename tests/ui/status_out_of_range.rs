@@ -0,0 +1,9 @@
+use erro_rs::errors;
+
+#[errors(std::io::Error(status = 600))]
+fn foo()
+{
+	Ok(())
+}
+
+fn main() {}
@@ -0,0 +1,31 @@
+use erro_rs::errors;
+
+trait Named
+{
+	fn name(&self) -> String;
+}
+
+struct File(String);
+
+#[errors]
+impl Named for File
+{
+	#[errors(std::fmt::Error)]
+	fn name(&self) -> String
+	{
+		Ok(self.0.clone())
+	}
+}
+
+struct Dir(String);
+
+#[errors(std::fmt::Error)]
+impl Named for Dir
+{
+	fn name(&self) -> String
+	{
+		Ok(self.0.clone())
+	}
+}
+
+fn main() {}
@@ -0,0 +1,9 @@
+use erro_rs::errors;
+
+#[errors("Box<dyn std::error::Error + Send + Sync>")]
+fn foo()
+{
+	Ok(())
+}
+
+fn main() {}
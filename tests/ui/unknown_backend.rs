@@ -0,0 +1,9 @@
+use erro_rs::errors;
+
+#[errors(backend = "anyhow", std::io::Error)]
+fn foo()
+{
+	Ok(())
+}
+
+fn main() {}
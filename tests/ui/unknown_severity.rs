@@ -0,0 +1,9 @@
+use erro_rs::errors;
+
+#[errors(std::io::Error(severity = "fatal"))]
+fn foo()
+{
+	Ok(())
+}
+
+fn main() {}
@@ -0,0 +1,21 @@
+use erro_rs::errors;
+
+mod fmt
+{
+	pub type Error = std::num::ParseIntError;
+}
+
+mod utf8
+{
+	pub type Error = std::string::FromUtf8Error;
+}
+
+// `crate::fmt::Error` cannot be lengthened past the alias of `std::fmt::Error`, but
+// `std::str::Utf8Error` and `crate::utf8::Error` are still named `StrUtf8` and `Utf8`
+#[errors(naming = "last", auto_disambiguate, std::fmt::Error = "Fmt", crate::fmt::Error, std::str::Utf8Error, crate::utf8::Error)]
+fn foo()
+{
+	Ok(())
+}
+
+fn main() {}
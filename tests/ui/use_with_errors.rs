@@ -0,0 +1,9 @@
+use erro_rs::errors;
+
+#[errors(use = Box<dyn std::error::Error>, std::io::Error)]
+fn foo()
+{
+	Ok(())
+}
+
+fn main() {}
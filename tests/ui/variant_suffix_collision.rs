@@ -0,0 +1,9 @@
+use erro_rs::errors;
+
+#[errors(variant_suffix = "Io", std::io::Error = "StdFmtIo", std::fmt::Error)]
+fn foo()
+{
+	Ok(())
+}
+
+fn main() {}
@@ -0,0 +1,9 @@
+use erro_rs::errors;
+
+#[errors(variant_suffix = "-Failed", std::io::Error)]
+fn foo()
+{
+	Ok(())
+}
+
+fn main() {}